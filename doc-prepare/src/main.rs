@@ -1,69 +1,353 @@
-use std::{fs::File, io::Read, path::PathBuf};
-use anyhow::Ok;
-use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use anyhow::{Context, bail};
+use roxmltree::{Document, Node, ParsingOptions};
+use serde::Serialize;
 
 const FN_DIR: &str = "../../lcdocs/sdk/script/fn/";
+const OUTPUT_FILE: &str = "../server/src/core/engine_functions.json";
 
-fn main() {
-    parse_fn_defs().unwrap();
-}
+/// Version of the artifact layout. Has to match the version the server expects.
+const FORMAT_VERSION: u32 = 1;
+
+/// Data types known to the language server, everything else is mapped to `any`.
+const KNOWN_TYPES: &[&str] = &["int", "id", "bool", "string", "object", "array", "map", "any"];
+
+/// Usage: `doc-prepare [fn_dir] [output_file]`
+fn main() -> anyhow::Result<()> {
+    let mut args = std::env::args().skip(1);
+    let fn_dir = args.next().unwrap_or_else(|| String::from(FN_DIR));
+    let output = args.next().unwrap_or_else(|| String::from(OUTPUT_FILE));
+
+    let functions = parse_fn_defs(Path::new(&fn_dir))?;
+    let artifact = DocArtifact {
+        version: FORMAT_VERSION,
+        functions,
+    };
 
-fn parse_fn_defs() -> anyhow::Result<()> {
+    let mut json = serde_json::to_string_pretty(&artifact)?;
+    json.push('\n');
+    std::fs::write(&output, json).with_context(|| format!("could not write {}", output))?;
 
-    let dir = std::fs::read_dir(FN_DIR)?;
+    println!("wrote {} functions to {}", artifact.functions.len(), output);
+    Ok(())
+}
 
-    for e in dir {
-        match e {
-            Result::Ok(entry) => {
-                let _ = parse_fn_file(&entry.path());
-            },
-            _ => {
-            },
+fn parse_fn_defs(dir: &Path) -> anyhow::Result<Vec<DocFn>> {
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)
+        .with_context(|| format!("could not read {}", dir.display()))?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|e| e == "xml"))
+        .collect();
+    paths.sort();
+
+    let mut functions = vec![];
+    for path in paths {
+        match parse_fn_file(&path) {
+            Ok(mut fns) => functions.append(&mut fns),
+            Err(e) => eprintln!("skipping {}: {:#}", path.display(), e),
         }
     }
 
-    Ok(())
+    functions.sort_by(|a, b| a.name.cmp(&b.name));
+    functions.dedup_by(|a, b| a.name == b.name);
+    Ok(functions)
 }
 
-#[derive(Debug, Deserialize)]
-pub struct DocFnFile {
-    //pub funcs: Vec<DocFn>
+#[derive(Debug, Serialize)]
+pub struct DocArtifact {
+    pub version: u32,
+    pub functions: Vec<DocFn>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct DocFn {
-    pub title: String,
-    pub desc: String,
-    pub syntax: DocFnSyntax,
+    pub name: String,
+    /// `None` for functions without a return value
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub return_type: Option<String>,
+    pub params: Vec<DocFnParam>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Engine version the function appeared in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub related: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub examples: Vec<DocFnExample>,
 }
 
-#[derive(Debug, Deserialize)]
-pub struct DocFnSyntax {
-    #[serde(rename(deserialize = "dataType"))]
-    pub return_type: String,
-    pub params: DocFnParams, 
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocFnParam {
+    pub name: String,
+    pub data_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub optional: bool,
 }
 
-#[derive(Debug, Deserialize)]
-pub struct DocFnParams {
-    pub param: Vec<DocFnParam>,
+#[derive(Debug, Serialize)]
+pub struct DocFnExample {
+    pub code: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
-pub struct DocFnParam {
-    #[serde(rename(deserialize = "type"))]
-    pub data_type: String,
-    pub name: String,
-    #[serde(rename(deserialize = "desc"))]
-    pub description: String,
+fn parse_fn_file(path: &PathBuf) -> anyhow::Result<Vec<DocFn>> {
+    let bytes = std::fs::read(path)?;
+    parse_fn_source(&decode(&bytes))
+}
+
+fn parse_fn_source(source: &str) -> anyhow::Result<Vec<DocFn>> {
+    let options = ParsingOptions {
+        allow_dtd: true,
+        ..Default::default()
+    };
+    let document = Document::parse_with_options(source, options)?;
+
+    let functions: Vec<DocFn> = document
+        .root_element()
+        .children()
+        .filter(|n| n.has_tag_name("func"))
+        .map(parse_fn)
+        .collect::<anyhow::Result<_>>()?;
+
+    if functions.is_empty() {
+        bail!("no <func> element");
+    }
+
+    Ok(functions)
+}
+
+fn parse_fn(func: Node) -> anyhow::Result<DocFn> {
+    let name = child(func, "title").map(prose).context("missing <title>")?;
+    let syntax = child(func, "syntax").context("missing <syntax>")?;
+
+    let params = child(syntax, "params")
+        .map(|params| {
+            params
+                .children()
+                .filter(|n| n.has_tag_name("param"))
+                .map(|p| DocFnParam {
+                    name: child(p, "name").map(prose).unwrap_or_default(),
+                    data_type: child(p, "type")
+                        .map(|t| normalize_type(&prose(t)))
+                        .unwrap_or_else(|| String::from("any")),
+                    description: child(p, "desc").map(prose).filter(|d| !d.is_empty()),
+                    optional: child(p, "optional").is_some(),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let return_type = child(syntax, "rtype")
+        .map(|t| prose(t).to_lowercase())
+        .filter(|t| !t.is_empty() && t != "void")
+        .map(|t| normalize_type(&t));
+
+    let related = child(func, "related")
+        .map(|r| r.descendants().filter(|n| n.has_tag_name("funclink")).map(prose).collect())
+        .unwrap_or_default();
+
+    let examples = child(func, "examples")
+        .map(|e| {
+            e.children()
+                .filter(|n| n.has_tag_name("example"))
+                .filter_map(|example| {
+                    Some(DocFnExample {
+                        code: code(child(example, "code")?),
+                        description: child(example, "text").map(prose).filter(|d| !d.is_empty()),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(DocFn {
+        name,
+        return_type,
+        params,
+        description: child(func, "desc").map(prose).filter(|d| !d.is_empty()),
+        version: child(func, "versions")
+            .and_then(|v| child(v, "version"))
+            .or_else(|| child(func, "version"))
+            .map(prose),
+        category: child(func, "category").map(prose),
+        related,
+        examples,
+    })
+}
+
+/// The docs are written in ISO-8859-1, newer files in UTF-8.
+fn decode(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(s) => s.to_string(),
+        Err(_) => bytes.iter().map(|b| *b as char).collect(),
+    }
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|n| n.has_tag_name(name))
 }
 
-fn parse_fn_file(path: &PathBuf) -> anyhow::Result<DocFnFile> {
+fn normalize_type(data_type: &str) -> String {
+    let data_type = data_type.trim().trim_end_matches('&').trim().to_lowercase();
+    match data_type.as_str() {
+        "dword" => String::from("int"),
+        t if KNOWN_TYPES.contains(&t) => data_type,
+        _ => String::from("any"),
+    }
+}
+
+/// Text of a description element as markdown on a single line.
+/// Bilingual elements are reduced to their english part.
+fn prose(node: Node) -> String {
+    let node = child(node, "en").unwrap_or(node);
+    let mut text = String::new();
+    collect_text(node, &mut text);
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
 
-    let mut s = String::new();
-    let mut f = File::open(path)?;
-    f.read_to_string(&mut s)?;
-    let document = serde_xml::from_str::<DocFnFile>(s.as_str())?;
+fn collect_text(node: Node, text: &mut String) {
+    for c in node.children() {
+        if c.is_text() {
+            text.push_str(c.text().unwrap_or_default());
+        } else if c.has_tag_name("funclink") || c.has_tag_name("code") {
+            let mut inner = String::new();
+            collect_text(c, &mut inner);
+            text.push_str(&format!("`{}`", inner.trim()));
+        } else if c.has_tag_name("br") {
+            text.push(' ');
+        } else if c.is_element() {
+            collect_text(c, text);
+        }
+    }
+}
+
+/// Text of a code element with its common indentation removed.
+fn code(node: Node) -> String {
+    let mut text = String::new();
+    collect_code(node, &mut text);
+
+    // blank lines around the code, e.g. the indentation in front of the closing tag
+    let lines: Vec<&str> = text
+        .trim_end()
+        .lines()
+        .skip_while(|l| l.trim().is_empty())
+        .map(|l| l.trim_end())
+        .collect();
+    let indent = lines
+        .iter()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .min()
+        .unwrap_or(0);
+
+    lines
+        .iter()
+        .map(|l| l.get(indent..).unwrap_or_default())
+        .collect::<Vec<&str>>()
+        .join("\n")
+}
 
-    Ok(document)
+fn collect_code(node: Node, text: &mut String) {
+    for c in node.children() {
+        if c.is_text() {
+            text.push_str(c.text().unwrap_or_default());
+        } else if c.is_element() {
+            collect_code(c, text);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const SAMPLE: &str = r#"<?xml version="1.0" encoding="ISO-8859-1"?>
+<!DOCTYPE funcs SYSTEM "../../../clonk.dtd">
+<funcs>
+  <func>
+    <title>CreateObject</title>
+    <category>Objects</category>
+    <version>4.6.5.0 CP</version>
+    <syntax>
+      <rtype>object</rtype>
+      <params>
+        <param>
+          <type>id</type>
+          <name>idType</name>
+          <desc>Type of the <funclink>object</funclink> to create.</desc>
+        </param>
+        <param>
+          <type>map</type>
+          <name>mProps</name>
+          <optional />
+        </param>
+        <param>
+          <type>dword&amp;</type>
+          <name>dwFlags</name>
+        </param>
+      </params>
+    </syntax>
+    <desc><en>Creates an object.</en><de>Erzeugt ein Objekt.</de></desc>
+    <examples>
+      <example>
+        <code>
+          if (x)
+            CreateObject(ROCK, 0, 0);
+        </code>
+        <text>Creates a rock.</text>
+      </example>
+    </examples>
+    <related><funclink>CreateContents</funclink></related>
+  </func>
+  <func>
+    <title>Explode</title>
+    <versions><version>4.9.1.0 CR</version><version>4.9.5.0 CR</version></versions>
+    <syntax><rtype>void</rtype></syntax>
+  </func>
+</funcs>
+"#;
+
+    #[test]
+    fn should_extract_functions_from_xml() {
+        let functions = parse_fn_source(SAMPLE).unwrap();
+        assert_eq!(functions.len(), 2);
+
+        let create = &functions[0];
+        assert_eq!(create.name, "CreateObject");
+        assert_eq!(create.return_type.as_deref(), Some("object"));
+        assert_eq!(create.version.as_deref(), Some("4.6.5.0 CP"));
+        assert_eq!(create.description.as_deref(), Some("Creates an object."));
+        assert_eq!(create.related, vec!["CreateContents"]);
+
+        let params: Vec<(&str, &str, bool)> = create
+            .params
+            .iter()
+            .map(|p| (p.name.as_str(), p.data_type.as_str(), p.optional))
+            .collect();
+        assert_eq!(params, vec![("idType", "id", false), ("mProps", "map", true), ("dwFlags", "int", false)]);
+        assert_eq!(create.params[0].description.as_deref(), Some("Type of the `object` to create."));
+
+        assert_eq!(create.examples.len(), 1);
+        assert_eq!(create.examples[0].code, "if (x)\n  CreateObject(ROCK, 0, 0);");
+        assert_eq!(create.examples[0].description.as_deref(), Some("Creates a rock."));
+
+        let explode = &functions[1];
+        assert_eq!(explode.return_type, None);
+        assert!(explode.params.is_empty());
+        assert_eq!(explode.version.as_deref(), Some("4.9.1.0 CR"));
+    }
+
+    #[test]
+    fn should_reject_files_without_functions() {
+        assert!(parse_fn_source("<funcs><author>x</author></funcs>").is_err());
+    }
 }
@@ -1,16 +1,19 @@
 use dashmap::DashMap;
 use legacy_clonk_ls::core::embedding::Embedding;
+use legacy_clonk_ls::core::lint::{LintConfig, CONFIG_FILE_NAME};
+use legacy_clonk_ls::core::project::Project;
 use legacy_clonk_ls::lang::Translation;
+use legacy_clonk_ls::lsp::asset_handler::HandlerContext;
+use legacy_clonk_ls::lsp::code_actions;
 use legacy_clonk_ls::lsp::doc::{DocType, Document};
 use legacy_clonk_ls::lsp::token_types::TokenTypes;
+use legacy_clonk_ls::lsp::workspace_symbols;
 use std::fs::OpenOptions;
 use std::sync::RwLock;
-use tokio;
-use tower_lsp::jsonrpc::Result;
+use tower_lsp::jsonrpc::{Error, Result};
 use tower_lsp::lsp_types::*;
 use tower_lsp::{Client, LanguageServer, LspService, Server};
 use tracing::info;
-use tree_sitter::{InputEdit, Point};
 
 struct OwnSemanticTokenType;
 
@@ -21,13 +24,13 @@ impl OwnSemanticTokenType {
 }
 
 const NEGOTIATE_TOKENT_TYPES: bool = false;
-const LOG_TREE_UPDATE_POSITIONS: bool = false;
 
 struct Backend {
     client: Client,
     token_types: RwLock<TokenTypes>,
     documents: DashMap<Url, Document>,
     embedding: Embedding,
+    project: RwLock<Project>,
 }
 
 impl Backend {
@@ -106,17 +109,14 @@ impl Backend {
     fn add_document(&self, uri: Url, contents: String) -> std::result::Result<(), String> {
         info!("add_document endpoint triggered. uri: {}", uri);
 
-        let doc_type;
-        match DocType::from_uri(&uri) {
-            Ok(dt) => {
-                doc_type = dt;
-            }
+        let doc_type = match DocType::from_uri(&uri) {
+            Ok(dt) => dt,
             Err(e) => {
                 let s = e.to_string();
                 tracing::error!("could not get doctype from url: {}", &s);
                 return Err(s);
             }
-        }
+        };
 
         info!("detected doctype for document: {:?}", &doc_type);
 
@@ -131,79 +131,19 @@ impl Backend {
         }
     }
 
-    fn change_document(&self, uri: &Url, contents: String) -> std::result::Result<(), String> {
-        if let Some(ref mut doc) = self.documents.get_mut(&uri) {
+    fn change_document(
+        &self,
+        uri: &Url,
+        changes: &[TextDocumentContentChangeEvent],
+    ) -> std::result::Result<(), String> {
+        if let Some(ref mut doc) = self.documents.get_mut(uri) {
             tracing::info!(
                 "Changed document {}, having doc type {:?}",
                 &uri,
                 &doc.doc_type
             );
-            let mut parser = doc.doc_type.get_parser().expect("Could not load language");
-
-            let start_byte = doc.tree.root_node().start_byte();
-            let old_end_byte = doc.tree.root_node().end_byte();
-            let old_end_position = doc.tree.root_node().end_position();
-            let new_end_row = contents.chars().filter(|c| c == &'\n').count();
-            let new_end_column = contents.chars().rev().position(|c| c == '\n').unwrap_or(0);
-
-            let old_tree = parser.parse(&doc.source, None).unwrap();
-            let old_r = old_tree.root_node();
-            let new_tree = parser.parse(&contents, None).unwrap();
-            let new_r = new_tree.root_node();
-
-            if LOG_TREE_UPDATE_POSITIONS {
-                tracing::info!(
-                    "         Old tree root positions: start: {}; end: {}; bytes_start: {}; bytes_end: {}",
-                    old_r.start_position(),
-                    old_r.end_position(),
-                    old_r.start_byte(),
-                    old_r.end_byte()
-                );
-                tracing::info!(
-                    "Computed old tree root positions: start: {}; end: {}; bytes_start: {}; bytes_end: {}",
-                    Point { row: 0, column: 0 },
-                    old_end_position,
-                    start_byte,
-                    old_end_byte,
-                );
-                tracing::info!(
-                    "         New tree root positions: start: {}; end: {}; bytes_start: {}; bytes_end: {}",
-                    new_r.start_position(),
-                    new_r.end_position(),
-                    new_r.start_byte(),
-                    new_r.end_byte()
-                );
-                tracing::info!(
-                    "Computed new tree root positions: start: {}; end: {}; bytes_start: {}; bytes_end: {}",
-                    Point { row: 0, column: 0 },
-                    Point {
-                        row: new_end_row,
-                        column: new_end_column,
-                    },
-                    start_byte,
-                    contents.len(),
-                );
-            }
-
-            doc.tree.edit(&InputEdit {
-                start_byte,
-                old_end_byte,
-                new_end_byte: contents.len(),
-                start_position: Point { row: 0, column: 0 },
-                old_end_position,
-                new_end_position: Point {
-                    row: new_end_row,
-                    column: new_end_column,
-                },
-            });
 
-            if let Some(new_tree) = parser.parse(&contents, Some(&doc.tree)) {
-                doc.tree = new_tree;
-                doc.source = contents;
-                Ok(())
-            } else {
-                Err(String::from("Could not update parse tree"))
-            }
+            doc.apply_changes(changes).map_err(|e| e.to_string())
         } else {
             Err(String::from("Could not get document"))
         }
@@ -212,6 +152,100 @@ impl Backend {
     fn drop_document(&self, uri: &Url) {
         self.documents.remove(uri);
     }
+
+    fn parse_workspace_roots(params: &InitializeParams) -> Vec<std::path::PathBuf> {
+        if let Some(ref folders) = params.workspace_folders {
+            folders
+                .iter()
+                .filter_map(|f| f.uri.to_file_path().ok())
+                .collect()
+        } else {
+            #[allow(deprecated)]
+            params
+                .root_uri
+                .as_ref()
+                .and_then(|uri| uri.to_file_path().ok())
+                .into_iter()
+                .collect()
+        }
+    }
+
+    /// Runs `f` with a snapshot of the document and the workspace state.
+    /// The document is cloned, so the document map is not locked while
+    /// handlers look at other open documents.
+    fn with_handler_context<T>(
+        &self,
+        uri: &Url,
+        endpoint: &str,
+        f: impl FnOnce(&Document, &HandlerContext) -> T,
+    ) -> Option<T> {
+        let Some(doc) = self.documents.get(uri).map(|d| d.clone()) else {
+            tracing::info!(
+                "Requested {} endpoint, but did not found document with uri: {}",
+                endpoint,
+                uri
+            );
+            return None;
+        };
+
+        let Ok(project) = self.project.read() else {
+            tracing::error!("Could not acquire project for {} endpoint", endpoint);
+            return None;
+        };
+
+        let ctx = HandlerContext {
+            project: &project,
+            embedding: &self.embedding,
+            documents: &self.documents,
+        };
+
+        Some(f(&doc, &ctx))
+    }
+
+    async fn publish_diagnostics(&self, uri: Url, version: Option<i32>) {
+        let diagnostics = self
+            .with_handler_context(&uri, "diagnostics", |doc, ctx| {
+                doc.doc_type.get_handler().get_diagnostics(doc, ctx)
+            })
+            .unwrap_or_default();
+
+        self.client
+            .publish_diagnostics(uri, diagnostics, version)
+            .await;
+    }
+
+    async fn register_file_watcher(&self) {
+        let options = DidChangeWatchedFilesRegistrationOptions {
+            watchers: vec![
+                FileSystemWatcher {
+                    glob_pattern: GlobPattern::String(String::from("**/*.c")),
+                    kind: None,
+                },
+                FileSystemWatcher {
+                    glob_pattern: GlobPattern::String(String::from("**/*.c4m")),
+                    kind: None,
+                },
+                FileSystemWatcher {
+                    glob_pattern: GlobPattern::String(String::from("**/{DefCore,Names}.txt")),
+                    kind: None,
+                },
+                FileSystemWatcher {
+                    glob_pattern: GlobPattern::String(format!("**/{}", CONFIG_FILE_NAME)),
+                    kind: None,
+                },
+            ],
+        };
+
+        let registration = Registration {
+            id: String::from("watch-scripts"),
+            method: String::from("workspace/didChangeWatchedFiles"),
+            register_options: serde_json::to_value(options).ok(),
+        };
+
+        if let Err(e) = self.client.register_capability(vec![registration]).await {
+            tracing::error!("Could not register file watcher: {}", e);
+        }
+    }
 }
 
 #[tower_lsp::async_trait]
@@ -242,22 +276,50 @@ impl LanguageServer for Backend {
         }
 
 
+        if let Ok(mut project) = self.project.write() {
+            project.set_roots(Self::parse_workspace_roots(&params));
+
+            if let Some(options) = params.initialization_options.clone() {
+                match LintConfig::from_json(options) {
+                    Ok(config) => project.set_lint_options(config),
+                    Err(e) => tracing::error!("Could not read lint configuration of initializationOptions: {}", e),
+                }
+            }
+        }
+
         if let Some(lang_tag) = params.locale {
             Translation::configure(lang_tag);
         }
 
         let text_document_sync_capabilities =
-            TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL);
+            TextDocumentSyncCapability::Kind(TextDocumentSyncKind::INCREMENTAL);
 
         Ok(InitializeResult {
             capabilities: ServerCapabilities {
                 semantic_tokens_provider: semantic_tokens_capabilities,
                 text_document_sync: Some(text_document_sync_capabilities),
                 hover_provider: Some(HoverProviderCapability::Simple(true)),
+                completion_provider: Some(CompletionOptions {
+                    trigger_characters: Some(vec![
+                        String::from(":"),
+                        String::from(">"),
+                        String::from("~"),
+                    ]),
+                    ..Default::default()
+                }),
+                definition_provider: Some(OneOf::Left(true)),
+                references_provider: Some(OneOf::Left(true)),
+                document_symbol_provider: Some(OneOf::Left(true)),
+                workspace_symbol_provider: Some(OneOf::Left(true)),
+                rename_provider: Some(OneOf::Right(RenameOptions {
+                    prepare_provider: Some(true),
+                    work_done_progress_options: Default::default(),
+                })),
                 signature_help_provider: Some(SignatureHelpOptions {
-                    //trigger_characters: Some(vec![String::from("(")]),
+                    trigger_characters: Some(vec![String::from("("), String::from(",")]),
                     ..Default::default()
                 }),
+                code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
                 ..Default::default()
             },
             ..Default::default()
@@ -265,9 +327,15 @@ impl LanguageServer for Backend {
     }
 
     async fn did_open(&self, params: DidOpenTextDocumentParams) {
-        if let Err(e) = self.add_document(params.text_document.uri, params.text_document.text) {
+        let uri = params.text_document.uri;
+        let version = params.text_document.version;
+
+        if let Err(e) = self.add_document(uri.clone(), params.text_document.text) {
             self.client.log_message(MessageType::INFO, e).await;
+            return;
         }
+
+        self.publish_diagnostics(uri, Some(version)).await;
     }
 
     async fn did_change(&self, params: DidChangeTextDocumentParams) {
@@ -275,14 +343,20 @@ impl LanguageServer for Backend {
 
         tracing::info!("Changes: {}", &params.content_changes.len());
 
-        if let Err(e) = self.change_document(&uri, params.content_changes[0].text.clone()) {
+        if let Err(e) = self.change_document(&uri, &params.content_changes) {
             tracing::error!("Error when updating text document ({:?}): {}", &uri, e);
             self.client.log_message(MessageType::INFO, e).await;
+            return;
         }
+
+        self.publish_diagnostics(uri, Some(params.text_document.version))
+            .await;
     }
 
     async fn did_close(&self, params: DidCloseTextDocumentParams) {
-        self.drop_document(&params.text_document.uri);
+        let uri = params.text_document.uri;
+        self.drop_document(&uri);
+        self.client.publish_diagnostics(uri, vec![], None).await;
     }
 
     async fn semantic_tokens_full(
@@ -322,6 +396,72 @@ impl LanguageServer for Backend {
         self.client
             .log_message(MessageType::INFO, "server initialized")
             .await;
+
+        let project = match self.project.read() {
+            Ok(project) => project.unindexed(),
+            Err(e) => {
+                tracing::error!("Could not acquire project for indexing: {}", e);
+                return;
+            }
+        };
+
+        // indexing reads the whole workspace, so requests are served by the old project meanwhile
+        let indexed = match tokio::task::spawn_blocking(move || {
+            let mut project = project;
+            let indexed = project.index_roots();
+            (project, indexed)
+        })
+        .await
+        {
+            Ok((project, indexed)) => {
+                match self.project.write() {
+                    Ok(mut current) => *current = project,
+                    Err(e) => tracing::error!("Could not acquire project for indexing: {}", e),
+                }
+                indexed
+            }
+            Err(e) => {
+                tracing::error!("Indexing the workspace failed: {}", e);
+                0
+            }
+        };
+
+        self.client
+            .log_message(MessageType::INFO, format!("indexed {} scripts", indexed))
+            .await;
+
+        self.register_file_watcher().await;
+    }
+
+    async fn did_change_watched_files(&self, params: DidChangeWatchedFilesParams) {
+        let lint_config_changed = {
+            let Ok(mut project) = self.project.write() else {
+                tracing::error!("Could not acquire project for updating the index");
+                return;
+            };
+
+            let mut lint_config_changed = false;
+            for change in params.changes {
+                let Ok(path) = change.uri.to_file_path() else {
+                    continue;
+                };
+
+                lint_config_changed |= project.is_lint_config_file(&path);
+                match change.typ {
+                    FileChangeType::DELETED => project.remove_file(&path),
+                    _ => project.update_file(&path),
+                }
+            }
+            lint_config_changed
+        };
+
+        // the levels of the lint rules apply to every open document
+        if lint_config_changed {
+            let uris: Vec<Url> = self.documents.iter().map(|d| d.key().clone()).collect();
+            for uri in uris {
+                self.publish_diagnostics(uri, None).await;
+            }
+        }
     }
 
     async fn shutdown(&self) -> Result<()> {
@@ -332,45 +472,132 @@ impl LanguageServer for Backend {
         Ok(())
     }
 
-    async fn completion(&self, p: CompletionParams) -> Result<Option<CompletionResponse>> {
-        self.client
-            .log_message(MessageType::INFO, format!("Got completion: {:?}", p))
-            .await;
+    async fn completion(&self, params: CompletionParams) -> Result<Option<CompletionResponse>> {
+        let uri = params.text_document_position.text_document.uri;
+        let position = params.text_document_position.position;
 
-        Ok(None)
+        Ok(self
+            .with_handler_context(&uri, "completion", |doc, ctx| {
+                doc.doc_type.get_handler().get_completion(doc, position, ctx)
+            })
+            .flatten())
     }
 
     async fn hover(&self, params: HoverParams) -> Result<Option<Hover>> {
         let uri = params.text_document_position_params.text_document.uri;
-        match self.documents.get(&uri) {
-            Some(doc) => {
-                let handler = doc.doc_type.get_handler();
-                if let Some(text) =
-                    handler.get_hover_text(&doc, params.text_document_position_params.position)
-                {
-                    let markup = MarkupContent {
-                        value: text,
-                        kind: MarkupKind::Markdown,
-                    };
-
-                    let contents = HoverContents::Markup(markup);
-                    let response = Hover {
-                        contents,
-                        range: None,
-                    };
-
-                    return Ok(Some(response));
-                }
-            }
-            _ => {
-                tracing::info!(
-                    "Requested hover endpoint, but did not found document with uri: {}",
-                    &uri
-                );
-            }
+        let position = params.text_document_position_params.position;
+
+        let text = self
+            .with_handler_context(&uri, "hover", |doc, ctx| {
+                doc.doc_type.get_handler().get_hover_text(doc, position, ctx)
+            })
+            .flatten();
+
+        Ok(text.map(|value| Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                value,
+                kind: MarkupKind::Markdown,
+            }),
+            range: None,
+        }))
+    }
+
+    async fn signature_help(&self, params: SignatureHelpParams) -> Result<Option<SignatureHelp>> {
+        let uri = params.text_document_position_params.text_document.uri;
+        let position = params.text_document_position_params.position;
+
+        Ok(self
+            .with_handler_context(&uri, "signature help", |doc, ctx| {
+                doc.doc_type.get_handler().get_signature_help(doc, position, ctx)
+            })
+            .flatten())
+    }
+
+    async fn code_action(&self, params: CodeActionParams) -> Result<Option<CodeActionResponse>> {
+        let actions = code_actions::quick_fixes(&params.text_document.uri, &params.context.diagnostics);
+        Ok(Some(actions))
+    }
+
+    async fn document_symbol(&self, params: DocumentSymbolParams) -> Result<Option<DocumentSymbolResponse>> {
+        let Some(doc) = self.documents.get(&params.text_document.uri).map(|d| d.clone()) else {
+            return Ok(None);
+        };
+
+        Ok(doc.doc_type.get_handler().get_document_symbols(&doc))
+    }
+
+    async fn symbol(&self, params: WorkspaceSymbolParams) -> Result<Option<Vec<SymbolInformation>>> {
+        let Ok(project) = self.project.read() else {
+            tracing::error!("Could not acquire project for workspace symbol endpoint");
+            return Ok(None);
+        };
+
+        let ctx = HandlerContext {
+            project: &project,
+            embedding: &self.embedding,
+            documents: &self.documents,
+        };
+
+        Ok(Some(workspace_symbols::get_workspace_symbols(&params.query, &ctx)))
+    }
+
+    async fn goto_definition(
+        &self,
+        params: GotoDefinitionParams,
+    ) -> Result<Option<GotoDefinitionResponse>> {
+        let uri = params.text_document_position_params.text_document.uri;
+        let position = params.text_document_position_params.position;
+
+        Ok(self
+            .with_handler_context(&uri, "definition", |doc, ctx| {
+                doc.doc_type.get_handler().get_definition(doc, position, ctx)
+            })
+            .flatten())
+    }
+
+    async fn references(&self, params: ReferenceParams) -> Result<Option<Vec<Location>>> {
+        let uri = params.text_document_position.text_document.uri;
+        let position = params.text_document_position.position;
+        let include_declaration = params.context.include_declaration;
+
+        Ok(self
+            .with_handler_context(&uri, "references", |doc, ctx| {
+                doc.doc_type
+                    .get_handler()
+                    .get_references(doc, position, include_declaration, ctx)
+            })
+            .flatten())
+    }
+
+    async fn prepare_rename(
+        &self,
+        params: TextDocumentPositionParams,
+    ) -> Result<Option<PrepareRenameResponse>> {
+        let Some(doc) = self.documents.get(&params.text_document.uri).map(|d| d.clone()) else {
+            return Ok(None);
+        };
+
+        match doc.doc_type.get_handler().prepare_rename(&doc, params.position) {
+            Ok(range) => Ok(range.map(PrepareRenameResponse::Range)),
+            Err(message) => Err(Error::invalid_params(message)),
         }
+    }
 
-        Ok(None)
+    async fn rename(&self, params: RenameParams) -> Result<Option<WorkspaceEdit>> {
+        let uri = params.text_document_position.text_document.uri;
+        let position = params.text_document_position.position;
+
+        let result = self.with_handler_context(&uri, "rename", |doc, ctx| {
+            doc.doc_type
+                .get_handler()
+                .rename(doc, position, &params.new_name, ctx)
+        });
+
+        match result {
+            Some(Ok(edit)) => Ok(edit),
+            Some(Err(message)) => Err(Error::invalid_params(message)),
+            None => Ok(None),
+        }
     }
 }
 
@@ -383,6 +610,7 @@ async fn start_language_server() {
         client,
         documents: DashMap::new(),
         embedding: Embedding::new(),
+        project: RwLock::new(Project::default()),
     });
     Server::new(stdin, stdout, socket).serve(service).await;
 }
@@ -0,0 +1,143 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use super::ini::IniDocument;
+
+pub const DEF_CORE_FILE_NAME: &str = "DefCore.txt";
+pub const NAMES_FILE_NAME: &str = "Names.txt";
+const SCRIPT_FILE_NAME: &str = "Script.c";
+
+/// Preferred languages of `Names.txt`, the first one found wins
+const NAME_LANGUAGES: [&str; 2] = ["US", "DE"];
+
+/// A definition folder, as described by its `DefCore.txt`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DefinitionInfo {
+    pub id: String,
+    pub folder: PathBuf,
+    /// Name from `Names.txt`, falling back to the `Name` of the DefCore
+    pub name: Option<String>,
+    /// `Category` as written, e.g. `C4D_Living|C4D_SelectMaterial`
+    pub category: Option<String>,
+    pub width: Option<i64>,
+    pub height: Option<i64>,
+}
+
+impl DefinitionInfo {
+    /// Reads the definition in the folder, `None` if it has no `DefCore.txt` with an `id`.
+    pub fn read(folder: &Path) -> Option<Self> {
+        let def_core = read_lossy(&folder.join(DEF_CORE_FILE_NAME))?;
+        let doc = IniDocument::parse(&def_core);
+        let get = |key: &str| {
+            doc.get("DefCore", key)
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(String::from)
+        };
+
+        let name = read_lossy(&folder.join(NAMES_FILE_NAME))
+            .and_then(|names| localized_name(&names))
+            .or_else(|| get("Name"));
+
+        Some(DefinitionInfo {
+            id: get("id")?,
+            folder: folder.to_path_buf(),
+            name,
+            category: get("Category"),
+            width: get("Width").and_then(|w| w.parse().ok()),
+            height: get("Height").and_then(|h| h.parse().ok()),
+        })
+    }
+
+    pub fn def_core_path(&self) -> PathBuf {
+        self.folder.join(DEF_CORE_FILE_NAME)
+    }
+
+    /// Script of the definition, if it has one
+    pub fn script_path(&self) -> Option<PathBuf> {
+        Some(self.folder.join(SCRIPT_FILE_NAME)).filter(|p| p.is_file())
+    }
+
+    /// Markdown description, with the folder relative to its workspace root
+    pub fn to_help_text(&self, roots: &[PathBuf]) -> String {
+        let mut text = match self.name {
+            Some(ref name) => format!("**{}** `{}`", name, self.id),
+            None => format!("`{}`", self.id),
+        };
+
+        if let Some(ref category) = self.category {
+            text.push_str(&format!("\n\nCategory: `{}`", category));
+        }
+
+        if let (Some(width), Some(height)) = (self.width, self.height) {
+            text.push_str(&format!("\n\nSize: {} x {}", width, height));
+        }
+
+        let folder = roots
+            .iter()
+            .find_map(|root| self.folder.strip_prefix(root).ok())
+            .unwrap_or(&self.folder);
+        text.push_str(&format!("\n\n({})", folder.display()));
+
+        text
+    }
+}
+
+fn read_lossy(path: &Path) -> Option<String> {
+    fs::read(path)
+        .ok()
+        .map(|content| String::from_utf8_lossy(&content).into_owned())
+}
+
+/// Name from lines like `US:Clonk`
+fn localized_name(names: &str) -> Option<String> {
+    let entries: Vec<(&str, &str)> = names
+        .lines()
+        .filter_map(|l| l.split_once(':'))
+        .map(|(lang, name)| (lang.trim(), name.trim()))
+        .filter(|(_, name)| !name.is_empty())
+        .collect();
+
+    NAME_LANGUAGES
+        .iter()
+        .find_map(|lang| entries.iter().find(|(l, _)| l == lang))
+        .or(entries.first())
+        .map(|(_, name)| name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn should_read_definition() {
+        let folder = std::env::temp_dir().join(format!("c4-definition-test-{}", std::process::id())).join("Clonk.c4d");
+        fs::create_dir_all(&folder).unwrap();
+        fs::write(
+            folder.join(DEF_CORE_FILE_NAME),
+            "[DefCore]\nid=CLNK\nName=Clonk\nCategory=C4D_Living|C4D_SelectMaterial\nWidth=12\nHeight=20\n",
+        )
+        .unwrap();
+
+        let info = DefinitionInfo::read(&folder).unwrap();
+        assert_eq!(info.id, "CLNK");
+        assert_eq!(info.name.as_deref(), Some("Clonk"));
+        assert_eq!((info.width, info.height), (Some(12), Some(20)));
+        assert!(info.script_path().is_none());
+
+        fs::write(folder.join(NAMES_FILE_NAME), "DE:Clonk (de)\r\nUS:Clonk (us)\r\n").unwrap();
+        let info = DefinitionInfo::read(&folder).unwrap();
+        assert_eq!(info.name.as_deref(), Some("Clonk (us)"));
+
+        let root = folder.parent().unwrap().to_path_buf();
+        assert_eq!(
+            info.to_help_text(std::slice::from_ref(&root)),
+            "**Clonk (us)** `CLNK`\n\nCategory: `C4D_Living|C4D_SelectMaterial`\n\nSize: 12 x 20\n\n(Clonk.c4d)"
+        );
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
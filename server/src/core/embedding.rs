@@ -4,40 +4,112 @@ use serde::Deserialize;
 use std::collections::HashMap;
 use tracing::info;
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct FnHelpParam {
     pub name: String,
     pub data_type: C4DataType,
-    // todo:
-    // reference
+    pub description: Option<String>,
+    #[serde(default)]
+    pub optional: bool,
+}
+
+impl FnHelpParam {
+    /// `...` stands for any number of additional parameters
+    pub fn is_variadic(&self) -> bool {
+        self.name == VARIADIC_PARAM
+    }
+
+    /// Parameter as written in a signature, e.g. `int iPlr`
+    pub fn to_label(&self) -> String {
+        if self.is_variadic() {
+            return self.name.clone();
+        }
+
+        format!("{} {}", self.data_type.moniker(), self.name)
+    }
 }
 
+#[derive(Deserialize)]
+pub struct FnExample {
+    pub code: String,
+    pub description: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct FnHelp {
+    /// `None` for functions without a return value
+    pub return_type: Option<C4DataType>,
     pub params: Vec<FnHelpParam>,
     pub description: Option<String>,
+    /// Engine version the function appeared in
+    pub version: Option<String>,
+    pub category: Option<String>,
+    #[serde(default)]
+    pub related: Vec<String>,
+    #[serde(default)]
+    pub examples: Vec<FnExample>,
+}
+
+#[derive(Deserialize)]
+struct FnHelpDto {
+    name: String,
+    #[serde(flatten)]
+    help: FnHelp,
+}
+
+/// Artifact generated by `doc-prepare` from the function documentation.
+#[derive(Deserialize)]
+struct EngineFunctionsDto {
+    version: u32,
+    functions: Vec<FnHelpDto>,
 }
 
 impl FnHelp {
-    pub fn to_help_text(&self, name: &String) -> String {
-        let mut params = String::new();
-        let mut seperate = false;
-        for p in self.params.iter() {
-            if seperate {
-                params.push_str(", ");
+    /// Signature without description, e.g. `func SetDir(int iDir, object pObj)`
+    pub fn to_signature(&self, name: &str) -> String {
+        let params: Vec<String> = self.params.iter().map(|p| p.to_label()).collect();
+        format!("func {}({})", name, params.join(", "))
+    }
+
+    pub fn to_help_text(&self, name: &str) -> String {
+        let mut text = format!("```c4script\n{}\n```", self.to_signature(name));
+
+        if let Some(ref d) = self.description {
+            text.push_str(&format!("\n\n{}", d));
+        }
+
+        let params: Vec<String> = self
+            .params
+            .iter()
+            .filter_map(|p| p.description.as_ref().map(|d| format!("- `{}`: {}", p.name, d)))
+            .collect();
+        if !params.is_empty() {
+            text.push_str(&format!("\n\n**Parameters**\n{}", params.join("\n")));
+        }
+
+        if let Some(ref t) = self.return_type {
+            text.push_str(&format!("\n\n**Returns** `{}`", t.moniker()));
+        }
+
+        for example in self.examples.iter() {
+            text.push_str(&format!("\n\n```c4script\n{}\n```", example.code));
+            if let Some(ref d) = example.description {
+                text.push_str(&format!("\n{}", d));
             }
+        }
 
-            // remove to_string()
-            params.push_str(p.data_type.moniker());
-            params.push(' ');
-            params.push_str(p.name.as_str());
-            seperate = true;
+        if !self.related.is_empty() {
+            let related: Vec<String> = self.related.iter().map(|r| format!("`{}`", r)).collect();
+            text.push_str(&format!("\n\nSee also: {}", related.join(", ")));
         }
 
-        let d = match self.description {
-            Some(ref d) => format!("\n\n{}", d),
-            _ => String::new(),
-        };
+        if let Some(ref v) = self.version {
+            text.push_str(&format!("\n\nSince {}", v));
+        }
 
-        format!("```c4script\nfunc {}({})```\n\n{}", name, params, d)
+        text
     }
 }
 
@@ -64,34 +136,113 @@ impl ConstantHelp {
 
 const CATEGORIES: &str = include_str!("./c4d_categories.json");
 
+/// Generated by `doc-prepare`, see there for the format
+const ENGINE_FUNCTIONS: &str = include_str!("./engine_functions.json");
+const ENGINE_FUNCTIONS_VERSION: u32 = 1;
+
+const VARIADIC_PARAM: &str = "...";
+
+/// Functions called by the engine by name. Renaming them breaks the
+/// script silently, as the engine would not call them anymore.
+const ENGINE_CALLBACKS: &[&str] = &[
+    "Initialize",
+    "InitializePlayer",
+    "RemovePlayer",
+    "Construction",
+    "Completion",
+    "Destruction",
+    "Redefine",
+    "Timer",
+    "Damage",
+    "Hit",
+    "Hit2",
+    "Hit3",
+    "Death",
+    "Incineration",
+    "IncinerationEx",
+    "Entrance",
+    "Departure",
+    "Collection",
+    "Collection2",
+    "Ejection",
+    "RejectCollect",
+    "RejectEntrance",
+    "Purchase",
+    "Sale",
+    "Activate",
+    "ActivateEntrance",
+    "Grabbed",
+    "GrabLost",
+    "CatchBlow",
+    "QueryCatchBlow",
+    "Stuck",
+    "LineBreak",
+    "Contact",
+    "ControlLeft",
+    "ControlRight",
+    "ControlUp",
+    "ControlDown",
+    "ControlDig",
+    "ControlThrow",
+    "ControlSpecial",
+    "ControlSpecial2",
+    "ControlCommand",
+    "ContainedLeft",
+    "ContainedRight",
+    "ContainedUp",
+    "ContainedDown",
+    "ContainedDig",
+    "ContainedThrow",
+    "ContextMenu",
+    "UpdateTransferZone",
+    "OnMenuSelection",
+    "MenuQueryCancel",
+    "OnGameOver",
+    "OnGoalsFulfilled",
+    "IsFulfilled",
+];
+
+/// Prefixes of the engine constants, of which only the `C4D_*` categories are documented so far
+const ENGINE_CONSTANT_PREFIXES: &[&str] = &[
+    "C4D_", "C4V_", "C4X_", "C4P_", "C4FO_", "C4SO_", "C4CMD_", "C4MN_", "C4Fx_", "FX_", "OCF_", "COMD_", "DIR_",
+    "VIS_", "GFX_", "GFXOV_", "PHYS_", "CNAT_",
+];
+
+/// Engine constants without one of the prefixes
+const ENGINE_CONSTANTS: &[&str] = &["NO_OWNER", "ANY_CONTAINER", "NO_CONTAINER"];
+
+/// Materials of the `Material.c4g` shipped with the engine
+const MATERIALS: &[&str] = &[
+    "Acid", "Ashes", "Coal", "Crystal", "DuroLava", "Earth", "FlyAshes", "Gold", "Granite", "Ice", "Lava", "Oil", "Ore",
+    "Rock", "Sand", "Snow", "Sulphur", "Vehicle", "Water",
+];
+
 pub struct Embedding {
     fn_help: HashMap<String, FnHelp>,
     cons_help: HashMap<String, ConstantHelp>,
 }
 
+impl Default for Embedding {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Embedding {
     pub fn new() -> Self {
-        let mut help = HashMap::<String, FnHelp>::new();
-
-        help.insert(String::from("InitializePlayer"), FnHelp { 
-            params: vec![FnHelpParam {
-                name: String::from("iPlr"),
-                data_type: C4DataType::Int,
-            }],
-            description: Some(String::from(r"After joining a new player the engine calls the function InitializePlayer in the scenario script for that player. This function is called after the basic player objects as defined in Scenario.txt have been placed, so a preliminary starting position has been selected and the player's crew and starting material and buildings are present.")),
-        });
-
-        help.insert(
-            String::from("RemovePlayer"),
-            FnHelp {
-                params: vec![FnHelpParam {
-                    name: String::from("iPlr"),
-                    data_type: C4DataType::Int,
-                }],
-                description: None,
-            },
+        let engine_functions = serde_json::from_str::<EngineFunctionsDto>(ENGINE_FUNCTIONS)
+            .expect("Could not parse engine function help");
+        assert_eq!(
+            engine_functions.version, ENGINE_FUNCTIONS_VERSION,
+            "Engine function help was generated for another version"
         );
 
+        let help: HashMap<String, FnHelp> = engine_functions
+            .functions
+            .into_iter()
+            .map(|f| (f.name, f.help))
+            .collect();
+
         let mut cons_help = HashMap::<String, ConstantHelp>::new();
 
         let cons_help_item = serde_json::from_str::<Vec<ConstantHelpDto>>(CATEGORIES)
@@ -107,6 +258,37 @@ impl Embedding {
         }
     }
 
+    pub fn functions(&self) -> impl Iterator<Item = (&String, &FnHelp)> {
+        self.fn_help.iter()
+    }
+
+    pub fn get_function(&self, name: &str) -> Option<&FnHelp> {
+        self.fn_help.get(name)
+    }
+
+    pub fn constants(&self) -> impl Iterator<Item = (&String, &ConstantHelp)> {
+        self.cons_help.iter()
+    }
+
+    pub fn get_constant(&self, name: &str) -> Option<&ConstantHelp> {
+        self.cons_help.get(name)
+    }
+
+    /// Whether the name is a documented constant or looks like one of the engine
+    pub fn is_engine_constant(&self, name: &str) -> bool {
+        self.cons_help.contains_key(name)
+            || ENGINE_CONSTANTS.contains(&name)
+            || ENGINE_CONSTANT_PREFIXES.iter().any(|prefix| name.starts_with(prefix))
+    }
+
+    pub fn is_engine_callback(name: &str) -> bool {
+        ENGINE_CALLBACKS.contains(&name)
+    }
+
+    pub fn materials() -> impl Iterator<Item = &'static str> {
+        MATERIALS.iter().copied()
+    }
+
     pub fn query_signature(&self, query: QueryableItem) -> Option<String> {
         match query {
             QueryableItem::Function(fn_name) => {
@@ -125,3 +307,34 @@ impl Embedding {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn should_load_engine_functions() {
+        let embedding = Embedding::new();
+
+        let create_object = embedding.get_function("CreateObject").unwrap();
+        assert!(matches!(create_object.return_type, Some(C4DataType::Object)));
+        assert_eq!(create_object.params[0].to_label(), "id idType");
+        assert!(create_object.params[1].optional);
+        assert!(!create_object.examples.is_empty());
+
+        let log = embedding.get_function("Log").unwrap();
+        assert_eq!(log.to_signature("Log"), "func Log(string szMessage, ...)");
+        assert!(log.params.last().unwrap().is_variadic());
+    }
+
+    #[test]
+    fn should_know_engine_constants() {
+        let embedding = Embedding::new();
+
+        assert!(embedding.is_engine_constant("C4D_Living"));
+        assert!(embedding.is_engine_constant("OCF_Collectible"));
+        assert!(embedding.is_engine_constant("NO_OWNER"));
+        assert!(!embedding.is_engine_constant("Count"));
+    }
+}
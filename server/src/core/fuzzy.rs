@@ -0,0 +1,74 @@
+/// Scores how well `candidate` matches `query`, or `None` if it does not match at all.
+///
+/// All characters of the query have to appear in the candidate in the same
+/// order (ignoring case). Matches at the start of the candidate, at word
+/// boundaries (`_`, camel case) and consecutive matches score higher, so
+/// `crobj` ranks `CreateObject` above `ScreenObject`.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i64> {
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let candidate: Vec<char> = candidate.chars().collect();
+    let mut score = 0;
+    let mut next = 0;
+    let mut prev_match: Option<usize> = None;
+
+    for q in query.chars() {
+        let idx = (next..candidate.len()).find(|&i| chars_match(q, candidate[i]))?;
+
+        score += 1;
+        if idx == 0 {
+            score += 8;
+        } else if is_word_start(&candidate, idx) {
+            score += 6;
+        }
+        if prev_match.is_some_and(|p| p + 1 == idx) {
+            score += 4;
+        }
+        if candidate[idx] == q {
+            score += 1;
+        }
+
+        prev_match = Some(idx);
+        next = idx + 1;
+    }
+
+    if query.chars().count() == candidate.len() {
+        score += 20;
+    }
+
+    // shorter candidates match the query more closely
+    Some(score * 100 - candidate.len() as i64)
+}
+
+fn chars_match(query: char, candidate: char) -> bool {
+    query.to_lowercase().eq(candidate.to_lowercase())
+}
+
+fn is_word_start(candidate: &[char], idx: usize) -> bool {
+    let prev = candidate[idx - 1];
+    let current = candidate[idx];
+
+    prev == '_' || (prev.is_lowercase() && current.is_uppercase()) || (!prev.is_ascii_digit() && current.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn should_rank_fuzzy_matches() {
+        assert!(fuzzy_score("xyz", "CreateObject").is_none());
+        assert!(fuzzy_score("", "CreateObject").is_some());
+
+        let create_object = fuzzy_score("crobj", "CreateObject").unwrap();
+        let screen_object = fuzzy_score("crobj", "ScreenObject").unwrap();
+        assert!(create_object > screen_object);
+
+        let exact = fuzzy_score("clnk", "CLNK").unwrap();
+        let longer = fuzzy_score("clnk", "CLNKX").unwrap();
+        assert!(exact > longer);
+    }
+}
@@ -0,0 +1,394 @@
+use std::ops::Range as ByteRange;
+
+use tower_lsp::lsp_types::Range;
+use tree_sitter::{Node, Point, Tree};
+
+use crate::core::parse::point_to_position;
+
+use super::{
+    node_kind::{
+        NODE_KIND_IDENTIFIER, NODE_KIND_NUMBER, NODE_KIND_PAIR, NODE_KIND_PROPERTY, NODE_KIND_SECTION,
+        NODE_KIND_SECTION_NAME, NODE_KIND_TEXT,
+    },
+    value_type::{is_id, ValueType},
+};
+
+/*
+The grammar reads values as atoms (numbers, identifiers and text), `KEY=count`
+pairs and the separators between them. Which separator delimits the entries
+depends on the type of the key:
+
+    Integer, Id, String, ...   Scalar     `50`
+    IntegerList                List       `1,2,3`
+    Category                   List       `C4D_Living|C4D_Object`
+    IdList, MatList            Pairs      `CLNK=1;FLAG=2`
+*/
+
+const LIST_SEPARATOR: &str = ",";
+const CATEGORY_SEPARATOR: &str = "|";
+const PAIR_SEPARATOR: &str = ";";
+const PAIR_OPERATOR: &str = "=";
+
+/// Typed view of an ini file, shared by the LSP handlers and [`super::IniDocument`].
+#[derive(Debug)]
+pub struct IniFile<'a> {
+    pub sections: Vec<Section<'a>>,
+}
+
+#[derive(Debug)]
+pub struct Section<'a> {
+    pub node: Node<'a>,
+    pub name: &'a str,
+    /// The identifier between the brackets
+    pub name_node: Node<'a>,
+    pub properties: Vec<Property<'a>>,
+}
+
+#[derive(Debug)]
+pub struct Property<'a> {
+    pub node: Node<'a>,
+    pub key: &'a str,
+    pub key_node: Node<'a>,
+    pub value: Option<Value<'a>>,
+}
+
+/// Everything behind the `=` of a property up to the end of the line
+#[derive(Debug)]
+pub struct Value<'a> {
+    pub node: Node<'a>,
+    pub text: &'a str,
+    /// Byte offset of `text` in the source
+    pub start: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AtomKind {
+    Integer,
+    /// `0x` followed by hex digits
+    Hex,
+    /// Four characters of `[_A-Z0-9]`
+    Id,
+    Identifier,
+    Text,
+    Empty,
+}
+
+/// Smallest part of a value, e.g. a number or an id
+#[derive(Debug, Clone, PartialEq)]
+pub struct Atom<'a> {
+    pub kind: AtomKind,
+    /// Text without surrounding whitespace
+    pub text: &'a str,
+    /// Absolute byte range of `text`
+    pub range: ByteRange<usize>,
+}
+
+/// `KEY=count` entry of an `IdList` or `MatList`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pair<'a> {
+    pub key: Atom<'a>,
+    /// Byte offset of the `=`
+    pub operator: Option<usize>,
+    pub count: Option<Atom<'a>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValueNode<'a> {
+    Scalar(Atom<'a>),
+    List {
+        items: Vec<Atom<'a>>,
+        /// Byte offsets of the separators
+        separators: Vec<usize>,
+    },
+    Pairs {
+        pairs: Vec<Pair<'a>>,
+        separators: Vec<usize>,
+    },
+}
+
+impl<'a> IniFile<'a> {
+    pub fn parse(tree: &'a Tree, source: &'a str) -> Self {
+        let source_bytes = source.as_bytes();
+        let root = tree.root_node();
+        let mut sections = vec![];
+
+        let mut cursor = root.walk();
+        for section in root.named_children(&mut cursor).filter(|n| n.kind() == NODE_KIND_SECTION) {
+            let (Some(name), Some(name_node)) = (
+                section_name(&section, source_bytes),
+                section.child(0).and_then(|n| n.child(1)),
+            ) else {
+                continue;
+            };
+
+            let mut properties = vec![];
+            let mut section_cursor = section.walk();
+            for property in section.named_children(&mut section_cursor).filter(|n| n.kind() == NODE_KIND_PROPERTY) {
+                let Some(key_node) = property_key(&property) else {
+                    continue;
+                };
+                let Ok(key) = key_node.utf8_text(source_bytes) else {
+                    continue;
+                };
+
+                let value = property_value(&property)
+                    .filter(|v| !v.is_missing())
+                    .and_then(|node| Value::from_node(node, source_bytes));
+
+                properties.push(Property {
+                    node: property,
+                    key,
+                    key_node,
+                    value,
+                });
+            }
+
+            sections.push(Section {
+                node: section,
+                name,
+                name_node,
+                properties,
+            });
+        }
+
+        IniFile { sections }
+    }
+
+    /// Property of the first section with the name. Files like ActMap.txt repeat sections,
+    /// use [`IniFile::sections`] for those.
+    pub fn property(&self, section: &str, key: &str) -> Option<&Property<'a>> {
+        let section = self.sections.iter().find(|s| s.name == section)?;
+        section.properties.iter().find(|p| p.key == key)
+    }
+
+    /// Section and property around a byte offset
+    pub fn property_at(&self, byte: usize) -> Option<(&Section<'a>, &Property<'a>)> {
+        self.sections.iter().find_map(|section| {
+            section
+                .properties
+                .iter()
+                .find(|p| p.node.start_byte() <= byte && byte <= p.node.end_byte())
+                .map(|p| (section, p))
+        })
+    }
+}
+
+impl<'a> Value<'a> {
+    fn from_node(node: Node<'a>, source: &'a [u8]) -> Option<Self> {
+        Some(Value {
+            node,
+            text: node.utf8_text(source).ok()?,
+            start: node.start_byte(),
+        })
+    }
+
+    pub fn parse(&self, value_type: ValueType) -> ValueNode<'a> {
+        match value_type {
+            ValueType::IntegerList => self.parse_list(LIST_SEPARATOR),
+            ValueType::Category => self.parse_list(CATEGORY_SEPARATOR),
+            ValueType::IdList | ValueType::MatList => self.parse_pairs(),
+            _ => ValueNode::Scalar(self.atom(&self.children(), self.start..self.end())),
+        }
+    }
+
+    /// Position of an absolute byte range inside of the value
+    pub fn range(&self, bytes: ByteRange<usize>, source: &[u8]) -> Range {
+        let start = self.node.start_byte();
+        value_range(&self.node, bytes.start - start..bytes.end - start, source)
+    }
+
+    fn end(&self) -> usize {
+        self.start + self.text.len()
+    }
+
+    fn children(&self) -> Vec<Node<'a>> {
+        let mut cursor = self.node.walk();
+        self.node.children(&mut cursor).collect()
+    }
+
+    fn parse_list(&self, sep: &str) -> ValueNode<'a> {
+        let (entries, separators) = self.split(sep);
+        let items = entries.into_iter().map(|(nodes, range)| self.atom(&nodes, range)).collect();
+
+        ValueNode::List { items, separators }
+    }
+
+    fn parse_pairs(&self) -> ValueNode<'a> {
+        let (entries, separators) = self.split(PAIR_SEPARATOR);
+        let pairs = entries
+            .into_iter()
+            .filter(|(nodes, _)| !nodes.is_empty())
+            .map(|(nodes, range)| self.pair(&nodes, range))
+            .collect();
+
+        ValueNode::Pairs { pairs, separators }
+    }
+
+    /// Nodes between the separators together with the byte range up to the
+    /// neighbouring separators, and the byte offsets of the separators
+    #[allow(clippy::type_complexity)]
+    fn split(&self, sep: &str) -> (Vec<(Vec<Node<'a>>, ByteRange<usize>)>, Vec<usize>) {
+        let mut entries = vec![];
+        let mut separators = vec![];
+        let mut nodes = vec![];
+        let mut start = self.start;
+
+        for child in self.children() {
+            if child.kind() == sep {
+                entries.push((std::mem::take(&mut nodes), start..child.start_byte()));
+                separators.push(child.start_byte());
+                start = child.end_byte();
+            } else {
+                nodes.push(child);
+            }
+        }
+        entries.push((nodes, start..self.end()));
+
+        (entries, separators)
+    }
+
+    /// Entry of an `IdList` or `MatList`. Entries which are no single pair,
+    /// e.g. `=1` or `REAC=1=2`, are split at their first `=`.
+    fn pair(&self, nodes: &[Node<'a>], range: ByteRange<usize>) -> Pair<'a> {
+        let mut parts = vec![];
+        for node in nodes {
+            if node.kind() == NODE_KIND_PAIR {
+                let mut cursor = node.walk();
+                parts.extend(node.children(&mut cursor));
+            } else {
+                parts.push(*node);
+            }
+        }
+
+        match parts.iter().position(|n| n.kind() == PAIR_OPERATOR) {
+            Some(idx) => {
+                let operator = parts[idx];
+                Pair {
+                    key: self.atom(&parts[..idx], range.start..operator.start_byte()),
+                    operator: Some(operator.start_byte()),
+                    count: Some(self.atom(&parts[idx + 1..], operator.end_byte()..range.end)),
+                }
+            }
+            None => Pair {
+                key: self.atom(&parts, range),
+                operator: None,
+                count: None,
+            },
+        }
+    }
+
+    /// Atom of a single number, identifier or text node, anything longer is text.
+    /// Without nodes the atom is empty and spans `empty`, so there is something to point at.
+    fn atom(&self, nodes: &[Node<'a>], empty: ByteRange<usize>) -> Atom<'a> {
+        let (Some(first), Some(last)) = (nodes.first(), nodes.last()) else {
+            return Atom {
+                kind: AtomKind::Empty,
+                text: "",
+                range: empty,
+            };
+        };
+
+        let range = first.start_byte()..last.end_byte();
+        let text = &self.text[range.start - self.start..range.end - self.start];
+
+        let kind = match (nodes.len(), first.kind()) {
+            (1, NODE_KIND_NUMBER) if text.starts_with("0x") => AtomKind::Hex,
+            (1, NODE_KIND_NUMBER) => AtomKind::Integer,
+            (1, NODE_KIND_IDENTIFIER | NODE_KIND_TEXT) if is_id(text) => AtomKind::Id,
+            (1, NODE_KIND_IDENTIFIER) => AtomKind::Identifier,
+            _ => AtomKind::Text,
+        };
+
+        Atom { kind, text, range }
+    }
+}
+
+/// Name of a section node, e.g. `Head` for `[Head]`
+fn section_name<'a>(section: &Node, source: &'a [u8]) -> Option<&'a str> {
+    let header = section.child(0).filter(|n| n.kind() == NODE_KIND_SECTION_NAME)?;
+    header.child(1)?.utf8_text(source).ok()
+}
+
+fn property_key<'a>(property: &Node<'a>) -> Option<Node<'a>> {
+    property.child(0)
+}
+
+fn property_value<'a>(property: &Node<'a>) -> Option<Node<'a>> {
+    property.named_child(0)
+}
+
+/// Range of a part of a (single line) value, given in bytes relative to the start of the value
+fn value_range(value: &Node, range: ByteRange<usize>, source: &[u8]) -> Range {
+    let start = value.start_position();
+    let position = |offset: usize| {
+        let point = Point {
+            row: start.row,
+            column: start.column + offset,
+        };
+        point_to_position(point, value.start_byte() + offset, source)
+    };
+
+    Range {
+        start: position(range.start),
+        end: position(range.end),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::core::ini::parse_tree;
+
+    #[test]
+    fn should_parse_values_by_type() {
+        let source = "[Game]\nRules=CLNK=2; Flag=x;\nCategory=C4D_Living | 64\nVersion=1,,Über 3\n";
+        let tree = parse_tree(source);
+        let file = IniFile::parse(&tree, source);
+        let value = |idx: usize| file.sections[0].properties[idx].value.as_ref().unwrap();
+
+        let ValueNode::Pairs { pairs, separators } = value(0).parse(ValueType::IdList) else {
+            panic!("expected pairs");
+        };
+        let flag = source.find("Flag").unwrap();
+
+        assert_eq!(separators, vec![source.find(';').unwrap(), source.rfind(';').unwrap()]);
+        assert_eq!(pairs.len(), 2);
+        assert_eq!(pairs[0].key.kind, AtomKind::Id);
+        assert_eq!(pairs[0].count.as_ref().unwrap().kind, AtomKind::Integer);
+        assert_eq!(pairs[1].key.text, "Flag");
+        assert_eq!(pairs[1].key.range, flag..flag + 4);
+        assert_eq!(pairs[1].operator, Some(flag + 4));
+
+        let ValueNode::List { items, .. } = value(1).parse(ValueType::Category) else {
+            panic!("expected list");
+        };
+        let number = source.find("64").unwrap();
+        assert_eq!(items.iter().map(|i| i.kind).collect::<Vec<AtomKind>>(), vec![AtomKind::Identifier, AtomKind::Integer]);
+        assert_eq!(items[1].range, number..number + 2);
+
+        let ValueNode::List { items, .. } = value(2).parse(ValueType::IntegerList) else {
+            panic!("expected list");
+        };
+        let empty = source.find(",,").unwrap() + 1;
+        assert_eq!(items.iter().map(|i| i.kind).collect::<Vec<AtomKind>>(), vec![AtomKind::Integer, AtomKind::Empty, AtomKind::Text]);
+        assert_eq!(items[1].range, empty..empty);
+        assert_eq!(items[2].text, "Über 3");
+    }
+
+    #[test]
+    fn should_build_file_from_tree() {
+        let source = "[Head]\nTitle=Über  \nIcon=1\n\n[Game]\nRules=REAC=1;\n";
+        let tree = parse_tree(source);
+        let file = IniFile::parse(&tree, source);
+
+        assert_eq!(file.sections.iter().map(|s| s.name).collect::<Vec<&str>>(), vec!["Head", "Game"]);
+
+        let title = file.sections[0].properties[0].value.as_ref().unwrap();
+        assert_eq!(title.text, "Über");
+        assert_eq!(title.range(title.start..title.start + title.text.len(), source.as_bytes()).end.character, 10);
+
+        let (section, property) = file.property_at(source.find("REAC").unwrap()).unwrap();
+        assert_eq!((section.name, property.key), ("Game", "Rules"));
+    }
+}
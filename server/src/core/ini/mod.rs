@@ -0,0 +1,315 @@
+use std::{
+    fmt::{self, Display},
+    ops::Range,
+};
+
+use tree_sitter::{Parser, Tree};
+
+use ast::{Atom, AtomKind, IniFile, Value, ValueNode};
+use schema::Schema;
+use value_type::ValueType;
+
+pub mod ast;
+pub mod node_kind;
+pub mod schema;
+pub mod schemas;
+pub mod value_type;
+
+/*
+Editable c4ini file, e.g. for tools rewriting Scenario.txt.
+
+The document keeps its source together with the syntax tree, which is read
+through the same `IniFile` the LSP handlers use. Edits replace the text of
+single values or lines and parse again, so comments, blank lines, unknown keys
+and broken lines stay as they are.
+*/
+
+#[derive(Debug, Clone)]
+pub struct IniDocument {
+    source: String,
+    tree: Tree,
+}
+
+/// Value of a property, resolved by the type of its key
+#[derive(Debug, Clone, PartialEq)]
+pub enum IniValue {
+    Integer(i64),
+    Dword(u32),
+    Boolean(bool),
+    IntegerList(Vec<i64>),
+    /// Ids or materials with an optional count
+    IdList(Vec<(String, Option<i64>)>),
+    MatList(Vec<(String, Option<i64>)>),
+    Id(String),
+    Category(Vec<String>),
+    /// Strings, procedures, actions and function names
+    Text(String),
+}
+
+fn parse_tree(source: &str) -> Tree {
+    let mut parser = Parser::new();
+    parser
+        .set_language(tree_sitter_c4ini::language())
+        .expect("Loading c4ini grammar");
+    parser.parse(source, None).expect("Parsing ini document")
+}
+
+impl IniDocument {
+    pub fn parse(source: &str) -> Self {
+        IniDocument {
+            source: source.to_string(),
+            tree: parse_tree(source),
+        }
+    }
+
+    /// Sections and properties of the document
+    pub fn file(&self) -> IniFile<'_> {
+        IniFile::parse(&self.tree, &self.source)
+    }
+
+    /// Value of a key in the first section of the name, empty for keys without value
+    pub fn get(&self, section: &str, key: &str) -> Option<&str> {
+        let file = self.file();
+        let property = file.property(section, key)?;
+        Some(property.value.as_ref().map_or("", |v| v.text))
+    }
+
+    /// Value of a key resolved against the schema, `None` for unknown
+    /// keys and values not matching the type of their key.
+    pub fn get_typed(&self, section: &str, key: &str, schema: &Schema) -> Option<IniValue> {
+        let def = schema.get_def(section, key)?;
+        let file = self.file();
+        IniValue::from_value(file.property(section, key)?.value.as_ref()?, def.value_type)
+    }
+
+    /// Sets the value of a key, adding the key and the section if missing.
+    pub fn set(&mut self, section: &str, key: &str, value: impl Display) {
+        let (range, text) = self.set_edit(section, key, &value.to_string());
+        self.edit(range, &text);
+    }
+
+    /// Range and text which replace or add the value
+    fn set_edit(&self, section: &str, key: &str, value: &str) -> (Range<usize>, String) {
+        let eol = self.line_ending();
+        let line = format!("{}={}", key, value);
+        let file = self.file();
+
+        let Some(s) = file.sections.iter().find(|s| s.name == section) else {
+            let end = self.source.len();
+            let mut text = String::new();
+            if end > 0 && !self.source.ends_with('\n') {
+                text.push_str(eol);
+            }
+            if !file.sections.is_empty() && !ends_with_blank_line(&self.source) {
+                text.push_str(eol);
+            }
+            text.push_str(&format!("[{}]{}{}{}", section, eol, line, eol));
+            return (end..end, text);
+        };
+
+        if let Some(property) = s.properties.iter().find(|p| p.key == key) {
+            let end = property.node.end_byte();
+            let range = property.value.as_ref().map_or(end..end, |v| v.start..v.start + v.text.len());
+            return (range, value.to_string());
+        }
+
+        // new keys go behind the last property, in front of trailing comments and blank lines
+        let last = s.properties.last().map_or(s.name_node, |p| p.node);
+        self.insert_line(last.end_byte(), &line, eol)
+    }
+
+    pub fn remove(&mut self, section: &str, key: &str) -> bool {
+        let Some((start, end)) = self
+            .file()
+            .property(section, key)
+            .map(|p| (p.node.start_byte(), p.node.end_byte()))
+        else {
+            return false;
+        };
+
+        // the whole line, including its line break
+        let start = self.source[..start].rfind('\n').map_or(0, |i| i + 1);
+        let end = self.source[end..].find('\n').map_or(self.source.len(), |i| end + i + 1);
+
+        self.edit(start..end, "");
+        true
+    }
+
+    /// Range and text to insert a line behind the line containing the byte
+    fn insert_line(&self, byte: usize, line: &str, eol: &str) -> (Range<usize>, String) {
+        match self.source[byte..].find('\n') {
+            Some(i) => (byte + i + 1..byte + i + 1, format!("{}{}", line, eol)),
+            None => (self.source.len()..self.source.len(), format!("{}{}{}", eol, line, eol)),
+        }
+    }
+
+    fn edit(&mut self, range: Range<usize>, text: &str) {
+        self.source.replace_range(range, text);
+        self.tree = parse_tree(&self.source);
+    }
+
+    /// Line ending used by the document, to keep files with `\r\n` consistent
+    fn line_ending(&self) -> &'static str {
+        match self.source.find('\n') {
+            Some(i) if self.source[..i].ends_with('\r') => "\r\n",
+            _ => "\n",
+        }
+    }
+}
+
+/// Whether the last terminated line of the text is blank
+fn ends_with_blank_line(text: &str) -> bool {
+    let mut lines = text.rsplit('\n');
+    lines.next();
+    lines.next().is_some_and(|l| l.trim().is_empty())
+}
+
+impl Display for IniDocument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+impl IniValue {
+    pub fn from_value(value: &Value, value_type: ValueType) -> Option<Self> {
+        let text = value.text;
+        let value = match (value_type, value.parse(value_type)) {
+            (ValueType::Integer, ValueNode::Scalar(atom)) => IniValue::Integer(integer(&atom)?),
+            (ValueType::Dword, ValueNode::Scalar(atom)) => IniValue::Dword(match atom.kind {
+                AtomKind::Hex => u32::from_str_radix(&atom.text[2..], 16).ok()?,
+                _ => atom.text.parse().ok()?,
+            }),
+            (ValueType::Boolean, ValueNode::Scalar(atom)) => match atom.text {
+                "0" => IniValue::Boolean(false),
+                "1" => IniValue::Boolean(true),
+                _ => return None,
+            },
+            (ValueType::IntegerList, ValueNode::List { items, .. }) => {
+                IniValue::IntegerList(items.iter().map(integer).collect::<Option<_>>()?)
+            }
+            (ValueType::IdList | ValueType::MatList, ValueNode::Pairs { pairs, .. }) => {
+                let entries = pairs
+                    .iter()
+                    .map(|p| {
+                        let count = match &p.count {
+                            Some(count) => Some(integer(count)?),
+                            None => None,
+                        };
+                        Some((p.key.text.to_string(), count))
+                    })
+                    .collect::<Option<_>>()?;
+
+                match value_type {
+                    ValueType::IdList => IniValue::IdList(entries),
+                    _ => IniValue::MatList(entries),
+                }
+            }
+            (ValueType::Id, ValueNode::Scalar(atom)) if atom.kind == AtomKind::Id => {
+                IniValue::Id(atom.text.to_string())
+            }
+            (ValueType::Category, ValueNode::List { items, .. }) => {
+                IniValue::Category(items.iter().map(|i| i.text.to_string()).collect())
+            }
+            (ValueType::String | ValueType::Procedure | ValueType::Action | ValueType::Function, _) => {
+                IniValue::Text(text.to_string())
+            }
+            _ => return None,
+        };
+
+        Some(value)
+    }
+}
+
+fn integer(atom: &Atom) -> Option<i64> {
+    match atom.kind {
+        AtomKind::Integer => atom.text.parse().ok(),
+        _ => None,
+    }
+}
+
+impl Display for IniValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let join = |parts: Vec<String>, sep: &str| parts.join(sep);
+        let pairs = |entries: &Vec<(String, Option<i64>)>| {
+            entries
+                .iter()
+                .map(|(key, count)| match count {
+                    Some(count) => format!("{}={}", key, count),
+                    None => key.clone(),
+                })
+                .collect::<Vec<String>>()
+        };
+
+        match self {
+            IniValue::Integer(i) => write!(f, "{}", i),
+            IniValue::Dword(d) => write!(f, "0x{:08X}", d),
+            IniValue::Boolean(b) => write!(f, "{}", *b as u8),
+            IniValue::IntegerList(list) => write!(f, "{}", join(list.iter().map(i64::to_string).collect(), ",")),
+            IniValue::IdList(entries) | IniValue::MatList(entries) => write!(f, "{}", join(pairs(entries), ";")),
+            IniValue::Id(id) => write!(f, "{}", id),
+            IniValue::Category(parts) => write!(f, "{}", parts.join("|")),
+            IniValue::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const SCENARIO: &str = "; generated by hand\r\n[Head]\r\nTitle = Über Test  \r\nVersion=4,9,5\r\n\r\n[Game]\r\n// rules\r\nRules=REAC=1;CNMT=1;\r\n\r\n";
+
+    #[test]
+    fn should_round_trip_unchanged_documents() {
+        for text in [SCENARIO, "", "no section\n[A]\nx=1", "[Broken\n  key = value\n[B]\n=\n"] {
+            assert_eq!(IniDocument::parse(text).to_string(), text);
+        }
+    }
+
+    #[test]
+    fn should_rewrite_values_and_keep_formatting() {
+        let schema = Schema::from_toml(
+            "[Head.keys]\nVersion = { type = \"IntegerList\", description = \"a\" }\n\n\
+             [Game.keys]\nRules = { type = \"IdList\", description = \"b\" }\n",
+        );
+        let mut doc = IniDocument::parse(SCENARIO);
+
+        assert_eq!(doc.get("Head", "Title"), Some("Über Test"));
+        assert_eq!(doc.get_typed("Head", "Version", &schema), Some(IniValue::IntegerList(vec![4, 9, 5])));
+
+        let Some(IniValue::IdList(mut rules)) = doc.get_typed("Game", "Rules", &schema) else {
+            panic!("expected rules");
+        };
+        rules.retain(|(id, _)| id != "CNMT");
+        rules.push((String::from("FLAG"), Some(2)));
+
+        doc.set("Head", "Version", IniValue::IntegerList(vec![4, 9, 6]));
+        doc.set("Game", "Rules", IniValue::IdList(rules));
+        doc.set("Game", "Mode", 1);
+        doc.set("Landscape", "MapWidth", 60);
+        assert!(doc.remove("Head", "Title"));
+
+        assert_eq!(
+            doc.to_string(),
+            "; generated by hand\r\n[Head]\r\nVersion=4,9,6\r\n\r\n[Game]\r\n// rules\r\nRules=REAC=1;FLAG=2\r\nMode=1\r\n\r\n[Landscape]\r\nMapWidth=60\r\n"
+        );
+    }
+
+    #[test]
+    fn should_add_keys_and_sections_to_unterminated_documents() {
+        let mut doc = IniDocument::parse("[A]\nx=1");
+
+        doc.set("A", "y", 2);
+        doc.set("B", "z", "C4D_Living|C4D_Object");
+        doc.set("A", "x", IniValue::IntegerList(vec![1, 2]));
+        assert_eq!(doc.to_string(), "[A]\nx=1,2\ny=2\n\n[B]\nz=C4D_Living|C4D_Object\n");
+
+        let schema = Schema::from_toml("[B.keys]\nz = { type = \"Category\", description = \"a\" }\n");
+        assert_eq!(
+            doc.get_typed("B", "z", &schema),
+            Some(IniValue::Category(vec![String::from("C4D_Living"), String::from("C4D_Object")]))
+        );
+        assert!(!doc.remove("B", "x"));
+    }
+}
@@ -1,5 +1,8 @@
 pub const NODE_KIND_SECTION: &str = "section";
 pub const NODE_KIND_SECTION_NAME: &str = "section_name";
 pub const NODE_KIND_PROPERTY: &str = "property";
+pub const NODE_KIND_VALUE: &str = "value";
+pub const NODE_KIND_PAIR: &str = "pair";
+pub const NODE_KIND_NUMBER: &str = "number";
 pub const NODE_KIND_IDENTIFIER: &str = "identifier";
-
+pub const NODE_KIND_TEXT: &str = "text";
@@ -0,0 +1,132 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use super::value_type::ValueType;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Definition {
+    #[serde(rename = "type")]
+    pub value_type: ValueType,
+    /// Translation key of the description
+    pub description: String,
+}
+
+/// Range of the number behind the name of a numbered section,
+/// e.g. `Player1`, `Player2`, ... Without `max`, any number is valid.
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct Numbered {
+    pub min: u32,
+    pub max: Option<u32>,
+}
+
+impl Numbered {
+    fn contains(&self, number: u32) -> bool {
+        number >= self.min && self.max.is_none_or(|max| number <= max)
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Section {
+    numbered: Option<Numbered>,
+    #[serde(default)]
+    keys: HashMap<String, Definition>,
+}
+
+/// Known sections and keys of an ini file.
+///
+/// Schemas are written in TOML, with a table per section:
+///
+/// ```toml
+/// [Player]
+/// numbered = { min = 1 }
+///
+/// [Player.keys]
+/// Wealth = { type = "Integer", description = "scenario_txt.playerX.wealth" }
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(transparent)]
+pub struct Schema {
+    sections: HashMap<String, Section>,
+}
+
+impl Schema {
+    pub fn from_toml(source: &str) -> Self {
+        toml::from_str(source).expect("Parsing schema")
+    }
+
+    /// Section by the name used in the file, e.g. `Player5` for `[Player]`
+    fn section(&self, section_name: &str) -> Option<&Section> {
+        if let Some(section) = self.sections.get(section_name).filter(|s| s.numbered.is_none()) {
+            return Some(section);
+        }
+
+        let digits = section_name.trim_start_matches(|c: char| !c.is_ascii_digit());
+        let base = &section_name[..section_name.len() - digits.len()];
+        let number = digits
+            .parse::<u32>()
+            .ok()
+            .filter(|_| digits.chars().all(|c| c.is_ascii_digit()))?;
+
+        self.sections
+            .get(base)
+            .filter(|s| s.numbered.is_some_and(|n| n.contains(number)))
+    }
+
+    pub fn get_def(&self, section_name: &str, key: &str) -> Option<&Definition> {
+        self.section(section_name).and_then(|s| s.keys.get(key))
+    }
+
+    pub fn has_section(&self, section_name: &str) -> bool {
+        self.section(section_name).is_some()
+    }
+
+    /// Names of sections which may be added to a file with the given sections.
+    /// Numbered sections are suggested with the lowest unused number.
+    pub fn section_names(&self, present: &[&str]) -> Vec<String> {
+        self.sections
+            .iter()
+            .filter_map(|(name, section)| match section.numbered {
+                None => Some(name.clone()),
+                Some(numbered) => (numbered.min..)
+                    .take_while(|n| numbered.contains(*n))
+                    .map(|n| format!("{}{}", name, n))
+                    .find(|candidate| !present.contains(&candidate.as_str())),
+            })
+            .collect()
+    }
+
+    pub fn keys(&self, section_name: &str) -> impl Iterator<Item = (&str, &Definition)> {
+        self.section(section_name)
+            .into_iter()
+            .flat_map(|s| s.keys.iter().map(|(k, d)| (k.as_str(), d)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn should_match_numbered_sections() {
+        let schema = Schema::from_toml(
+            "[Head.keys]\nTitle = { type = \"String\", description = \"a\" }\n\n\
+             [Player]\nnumbered = { min = 1 }\n\n[Player.keys]\nWealth = { type = \"Integer\", description = \"b\" }\n\n\
+             [Team]\nnumbered = { min = 1, max = 2 }\n",
+        );
+
+        assert!(schema.get_def("Head", "Title").is_some());
+        assert_eq!(schema.get_def("Player12", "Wealth").unwrap().value_type, ValueType::Integer);
+        assert!(schema.has_section("Player1"));
+        assert!(!schema.has_section("Player"));
+        assert!(!schema.has_section("Player0"));
+        assert!(!schema.has_section("Player+1"));
+        assert!(schema.has_section("Team2"));
+        assert!(!schema.has_section("Team3"));
+
+        let mut names = schema.section_names(&["Head", "Player1", "Player2", "Team1", "Team2"]);
+        names.sort();
+        assert_eq!(names, vec!["Head", "Player3"]);
+    }
+}
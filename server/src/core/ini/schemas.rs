@@ -0,0 +1,16 @@
+use lazy_static::lazy_static;
+
+use super::schema::Schema;
+
+lazy_static! {
+    /// `DefCore.txt` of object definitions
+    pub static ref DEF_CORE: Schema = Schema::from_toml(include_str!("./schemas/def_core.toml"));
+    /// `Scenario.txt` of scenarios
+    pub static ref SCENARIO_TXT: Schema = Schema::from_toml(include_str!("./schemas/scenario_txt.toml"));
+    /// `ActMap.txt` of object definitions
+    pub static ref ACT_MAP: Schema = Schema::from_toml(include_str!("./schemas/act_map.toml"));
+    /// Material definitions (`*.c4m`) inside of `Material.c4g`
+    pub static ref MATERIAL: Schema = Schema::from_toml(include_str!("./schemas/material.toml"));
+    /// `Particle.txt` (or `Particles.txt`) of particle definitions
+    pub static ref PARTICLE: Schema = Schema::from_toml(include_str!("./schemas/particle.toml"));
+}
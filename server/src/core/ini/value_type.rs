@@ -0,0 +1,46 @@
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum ValueType {
+    Integer,
+    #[serde(rename = "DWORD")]
+    Dword,
+    /// `0` or `1`
+    Boolean,
+    IntegerList,
+    IdList,
+    MatList,
+    Id,
+    String,
+    /// `C4D_*` constants and numbers combined with `|`
+    Category,
+    /// Engine procedure of an action, e.g. `WALK`
+    Procedure,
+    /// Name of an action of the same ActMap.txt
+    Action,
+    /// Name of a script function
+    Function,
+}
+
+impl ValueType {
+    pub fn moniker(&self) -> &'static str {
+        match self {
+            ValueType::Integer => "Integer",
+            ValueType::Dword => "DWORD",
+            ValueType::Boolean => "Boolean",
+            ValueType::IntegerList => "IntegerList",
+            ValueType::IdList => "IdList",
+            ValueType::MatList => "MatList",
+            ValueType::Id => "Id",
+            ValueType::String => "String",
+            ValueType::Category => "Category",
+            ValueType::Procedure => "Procedure",
+            ValueType::Action => "Action",
+            ValueType::Function => "Function",
+        }
+    }
+}
+
+pub fn is_id(value: &str) -> bool {
+    value.len() == 4 && value.chars().all(|c| c == '_' || c.is_ascii_uppercase() || c.is_ascii_digit())
+}
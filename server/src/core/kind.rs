@@ -1,2 +1,34 @@
 pub const NODE_KIND_SOURCE_FILE: &str = "source_file";
 pub const NODE_KIND_FN_DEF: &str = "function_definition";
+pub const NODE_KIND_INCLUDE: &str = "include";
+pub const NODE_KIND_APPENDTO: &str = "appendto";
+pub const NODE_KIND_METHOD_CALL: &str = "method_call";
+pub const NODE_KIND_ARROW_EXPRESSION: &str = "arrow_expression";
+pub const NODE_KIND_IDENTIFIER: &str = "identifier";
+pub const NODE_KIND_ID: &str = "id";
+pub const NODE_KIND_VAR_DEFINITION: &str = "var_definition";
+pub const NODE_KIND_VAR_DEFINITION_INLINE: &str = "var_definition_inline";
+pub const NODE_KIND_VAR_ASSIGNMENT: &str = "var_assignment";
+pub const NODE_KIND_VAR_SCOPE: &str = "var_scope";
+pub const NODE_KIND_PARAM: &str = "param";
+pub const NODE_KIND_FOR_STATEMENT: &str = "for_statement";
+pub const NODE_KIND_MAP_ACCESS: &str = "map_access";
+pub const NODE_KIND_MAP_KEY: &str = "map_key";
+pub const NODE_KIND_ARGS_LIST: &str = "args_list";
+pub const NODE_KIND_COMMENT: &str = "comment";
+pub const NODE_KIND_FUNC_DESC: &str = "func_desc";
+pub const NODE_KIND_NUMBER: &str = "number";
+pub const NODE_KIND_STRING: &str = "string";
+pub const NODE_KIND_BOOL: &str = "bool";
+pub const NODE_KIND_NIL: &str = "nil";
+pub const NODE_KIND_ARRAY: &str = "array";
+pub const NODE_KIND_MAP: &str = "map";
+pub const NODE_KIND_TRANSLATION: &str = "translation";
+pub const NODE_KIND_BUILTIN_CONSTANT: &str = "builtin_constant";
+pub const NODE_KIND_UNARY_EXPRESSION: &str = "unary_expression";
+pub const NODE_KIND_BINARY_EXPRESSION: &str = "binary_expression";
+pub const NODE_KIND_PRAGMA_STRICT: &str = "pragma_strict";
+pub const NODE_KIND_BLOCK: &str = "block";
+pub const NODE_KIND_IF_STATEMENT: &str = "if_statement";
+pub const NODE_KIND_RETURN_STATEMENT: &str = "return_statement";
+pub const NODE_KIND_PARAMETER_LIST: &str = "parameter_list";
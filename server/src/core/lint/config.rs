@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+use super::{rule, LintRule};
+
+/// Configuration file in a workspace root, which overrides the `initializationOptions`
+pub const CONFIG_FILE_NAME: &str = ".legacyclonk-ls.toml";
+
+/// How findings of a rule are reported
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    Off,
+    Hint,
+    Info,
+    Warning,
+    Error,
+}
+
+impl Level {
+    /// `None` if the rule is turned off
+    pub fn severity(&self) -> Option<DiagnosticSeverity> {
+        match self {
+            Level::Off => None,
+            Level::Hint => Some(DiagnosticSeverity::HINT),
+            Level::Info => Some(DiagnosticSeverity::INFORMATION),
+            Level::Warning => Some(DiagnosticSeverity::WARNING),
+            Level::Error => Some(DiagnosticSeverity::ERROR),
+        }
+    }
+}
+
+/// Levels of the rules, which differ from their default, e.g.
+///
+/// ```toml
+/// [lint]
+/// unused-param = "off"
+/// empty-block = "warning"
+/// ```
+///
+/// The `initializationOptions` have the same structure.
+#[derive(Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(transparent)]
+pub struct LintConfig {
+    levels: HashMap<String, Level>,
+}
+
+/// Settings of the server, of which only the lint levels are known so far
+#[derive(Deserialize, Default)]
+struct Settings {
+    #[serde(default)]
+    lint: LintConfig,
+}
+
+impl LintConfig {
+    pub fn from_toml(text: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str::<Settings>(text).map(|s| s.lint.checked())
+    }
+
+    pub fn from_json(value: serde_json::Value) -> Result<Self, serde_json::Error> {
+        serde_json::from_value::<Settings>(value).map(|s| s.lint.checked())
+    }
+
+    pub fn level(&self, rule: &dyn LintRule) -> Level {
+        self.levels.get(rule.name()).copied().unwrap_or(rule.default_level())
+    }
+
+    /// Levels of `other` take precedence.
+    pub fn merge(&mut self, other: LintConfig) {
+        self.levels.extend(other.levels);
+    }
+
+    /// Unknown rules are ignored, but mentioned in the log as they are likely typos.
+    fn checked(self) -> Self {
+        for name in self.levels.keys().filter(|name| rule(name).is_none()) {
+            tracing::warn!("Unknown lint rule in configuration: {}", name);
+        }
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::core::lint::RULES;
+
+    #[test]
+    fn should_read_config() {
+        let unused_param = rule("unused-param").unwrap();
+        let empty_block = rule("empty-block").unwrap();
+
+        let mut config = LintConfig::from_json(serde_json::json!({
+            "lint": { "unused-param": "off", "empty-block": "error" },
+            "other": true
+        }))
+        .unwrap();
+        assert_eq!(config.level(unused_param), Level::Off);
+
+        config.merge(LintConfig::from_toml("[lint]\nempty-block = \"hint\"\n").unwrap());
+        assert_eq!(config.level(unused_param), Level::Off);
+        assert_eq!(config.level(empty_block), Level::Hint);
+
+        assert!(LintConfig::from_toml("[lint]\nempty-block = \"loud\"\n").is_err());
+        assert_eq!(LintConfig::from_toml("").unwrap(), LintConfig::default());
+        assert!(RULES.iter().all(|r| LintConfig::default().level(*r) == r.default_level()));
+    }
+}
@@ -0,0 +1,156 @@
+mod config;
+mod rules;
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use tower_lsp::lsp_types::{DiagnosticSeverity, Range, TextEdit};
+use tree_sitter::{Node, Tree};
+
+use super::signatures::{C4DataType, VarScope};
+
+pub use config::{Level, LintConfig, CONFIG_FILE_NAME};
+
+/// All rules, which are run on every script
+pub const RULES: &[&dyn LintRule] = &[
+    &rules::UnusedParam,
+    &rules::UnusedVar,
+    &rules::UnreachableCode,
+    &rules::AssignmentInIf,
+    &rules::EmptyBlock,
+    &rules::ShadowedLocal,
+    &rules::ForInNonArray,
+];
+
+/// Types of expressions, as far as they are known
+pub trait ExpressionTypes {
+    fn type_of(&self, node: &Node) -> Option<C4DataType>;
+}
+
+/// Everything a rule may look at
+pub struct LintContext<'a> {
+    pub tree: &'a Tree,
+    pub source: &'a [u8],
+    /// `local`s and `static`s declared in the script and the scripts it includes
+    pub script_variables: HashMap<&'a str, VarScope>,
+    pub types: &'a dyn ExpressionTypes,
+}
+
+pub trait LintRule: Sync {
+    /// Name of the rule in the configuration, which is the code of its diagnostics as well
+    fn name(&self) -> &'static str;
+    fn default_level(&self) -> Level;
+    fn check(&self, ctx: &LintContext, findings: &mut Vec<Finding>);
+}
+
+/// Something a rule complains about
+#[derive(Debug)]
+pub struct Finding {
+    pub range: Range,
+    pub message: String,
+    /// Whether the code can be removed, which editors usually show faded out
+    pub unnecessary: bool,
+    pub fix: Option<Fix>,
+}
+
+impl Finding {
+    pub fn new(range: Range, message: String) -> Self {
+        Finding {
+            range,
+            message,
+            unnecessary: false,
+            fix: None,
+        }
+    }
+
+    pub fn unnecessary(mut self) -> Self {
+        self.unnecessary = true;
+        self
+    }
+
+    pub fn with_fix(mut self, fix: Fix) -> Self {
+        self.fix = Some(fix);
+        self
+    }
+}
+
+/// Edits resolving a finding. They are passed to the client as `data` of the
+/// diagnostic, so code actions can be created without linting again.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Fix {
+    pub title: String,
+    pub edits: Vec<TextEdit>,
+}
+
+/// Finding of a rule with the severity configured for the rule
+#[derive(Debug)]
+pub struct Report {
+    /// Name of the rule
+    pub code: &'static str,
+    pub severity: DiagnosticSeverity,
+    pub finding: Finding,
+}
+
+/// Runs all rules, which are not turned off, on the script.
+pub fn run(ctx: &LintContext, config: &LintConfig) -> Vec<Report> {
+    let mut reports = vec![];
+
+    for rule in RULES {
+        let Some(severity) = config.level(*rule).severity() else {
+            continue;
+        };
+
+        let mut findings = vec![];
+        rule.check(ctx, &mut findings);
+
+        reports.extend(findings.into_iter().map(|finding| Report {
+            code: rule.name(),
+            severity,
+            finding,
+        }));
+    }
+
+    reports
+}
+
+pub fn rule(name: &str) -> Option<&'static dyn LintRule> {
+    RULES.iter().find(|r| r.name() == name).copied()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::lsp::doc::DocType;
+
+    struct NoTypes;
+
+    impl ExpressionTypes for NoTypes {
+        fn type_of(&self, _: &Node) -> Option<C4DataType> {
+            None
+        }
+    }
+
+    #[test]
+    fn should_run_configured_rules() {
+        let source = "func Foo(a) {\n  if (a = 1) {}\n}\n";
+        let tree = DocType::Script.get_parser().unwrap().parse(source, None).unwrap();
+        let ctx = LintContext {
+            tree: &tree,
+            source: source.as_bytes(),
+            script_variables: HashMap::new(),
+            types: &NoTypes,
+        };
+
+        let reports = run(&ctx, &LintConfig::default());
+        let codes: Vec<&str> = reports.iter().map(|r| r.code).collect();
+        assert_eq!(codes, vec!["assignment-in-if", "empty-block"]);
+        assert_eq!(reports[0].severity, DiagnosticSeverity::WARNING);
+        assert!(reports[0].finding.fix.is_some());
+
+        let config = LintConfig::from_toml("[lint]\nassignment-in-if = \"error\"\nempty-block = \"off\"\n").unwrap();
+        let reports = run(&ctx, &config);
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].severity, DiagnosticSeverity::ERROR);
+    }
+}
@@ -0,0 +1,439 @@
+use std::collections::HashSet;
+
+use tower_lsp::lsp_types::{Range, TextEdit};
+use tree_sitter::Node;
+
+use crate::core::{
+    kind::{
+        NODE_KIND_ARROW_EXPRESSION, NODE_KIND_BINARY_EXPRESSION, NODE_KIND_BLOCK, NODE_KIND_COMMENT, NODE_KIND_FN_DEF,
+        NODE_KIND_FOR_STATEMENT, NODE_KIND_IDENTIFIER, NODE_KIND_IF_STATEMENT, NODE_KIND_METHOD_CALL,
+        NODE_KIND_PARAM, NODE_KIND_RETURN_STATEMENT, NODE_KIND_VAR_ASSIGNMENT,
+    },
+    parse::{forwards_params, is_declaration, is_variable, node_range, operator, var_scope_of},
+    signatures::C4DataType,
+};
+
+use super::{Finding, Fix, Level, LintContext, LintRule};
+
+pub struct UnusedParam;
+
+impl LintRule for UnusedParam {
+    fn name(&self) -> &'static str {
+        "unused-param"
+    }
+
+    // callbacks of the engine often do not need all of their parameters
+    fn default_level(&self) -> Level {
+        Level::Hint
+    }
+
+    fn check(&self, ctx: &LintContext, findings: &mut Vec<Finding>) {
+        for function in functions(ctx) {
+            let variables = FunctionVariables::collect(&function, ctx.source);
+            if variables.forwards {
+                continue;
+            }
+
+            for (param, name) in variables.params.iter() {
+                if !variables.used.contains(name) {
+                    let message = format!("parameter '{}' is never used", name);
+                    findings.push(Finding::new(node_range(param, ctx.source), message).unnecessary());
+                }
+            }
+        }
+    }
+}
+
+pub struct UnusedVar;
+
+impl LintRule for UnusedVar {
+    fn name(&self) -> &'static str {
+        "unused-var"
+    }
+
+    fn default_level(&self) -> Level {
+        Level::Warning
+    }
+
+    fn check(&self, ctx: &LintContext, findings: &mut Vec<Finding>) {
+        for function in functions(ctx) {
+            let variables = FunctionVariables::collect(&function, ctx.source);
+
+            for (var, name) in variables.vars.iter() {
+                if !variables.used.contains(name) {
+                    let message = format!("variable '{}' is never used", name);
+                    findings.push(Finding::new(node_range(var, ctx.source), message).unnecessary());
+                }
+            }
+        }
+    }
+}
+
+pub struct UnreachableCode;
+
+impl LintRule for UnreachableCode {
+    fn name(&self) -> &'static str {
+        "unreachable-code"
+    }
+
+    fn default_level(&self) -> Level {
+        Level::Warning
+    }
+
+    fn check(&self, ctx: &LintContext, findings: &mut Vec<Finding>) {
+        visit(&ctx.tree.root_node(), &mut |block| {
+            if block.kind() != NODE_KIND_BLOCK {
+                return;
+            }
+
+            let mut cursor = block.walk();
+            let statements: Vec<Node> = block
+                .children(&mut cursor)
+                .filter(|c| !matches!(c.kind(), "{" | "}" | NODE_KIND_COMMENT))
+                .collect();
+
+            let Some(ret) = statements.iter().position(|s| s.kind() == NODE_KIND_RETURN_STATEMENT) else {
+                return;
+            };
+
+            // the `;` of the `return` itself is not unreachable
+            let unreachable = &statements[ret + 1..];
+            let Some(first) = unreachable.iter().find(|s| s.is_named()) else {
+                return;
+            };
+            let last = unreachable.last().unwrap_or(first);
+
+            let range = Range::new(node_range(first, ctx.source).start, node_range(last, ctx.source).end);
+            let removal = Range::new(
+                first.prev_sibling().map_or(range.start, |p| node_range(&p, ctx.source).end),
+                range.end,
+            );
+
+            let fix = Fix {
+                title: String::from("Remove unreachable code"),
+                edits: vec![TextEdit::new(removal, String::new())],
+            };
+            findings.push(
+                Finding::new(range, String::from("unreachable code after 'return'"))
+                    .unnecessary()
+                    .with_fix(fix),
+            );
+        });
+    }
+}
+
+pub struct AssignmentInIf;
+
+impl LintRule for AssignmentInIf {
+    fn name(&self) -> &'static str {
+        "assignment-in-if"
+    }
+
+    fn default_level(&self) -> Level {
+        Level::Warning
+    }
+
+    fn check(&self, ctx: &LintContext, findings: &mut Vec<Finding>) {
+        visit(&ctx.tree.root_node(), &mut |node| {
+            let Some(condition) = Some(node)
+                .filter(|n| n.kind() == NODE_KIND_IF_STATEMENT)
+                .and_then(|n| n.named_child(0))
+                .filter(|c| c.kind() == NODE_KIND_BINARY_EXPRESSION && operator(c) == Some("="))
+            else {
+                return;
+            };
+
+            // `if (obj = FindObject(...))` is the usual way to test a result,
+            // and additional parentheses mark the assignment as intended
+            let mut cursor = node.walk();
+            let parens = node
+                .children(&mut cursor)
+                .take_while(|c| c.id() != condition.id())
+                .filter(|c| c.kind() == "(")
+                .count();
+            let call = condition
+                .named_child(1)
+                .is_some_and(|r| matches!(r.kind(), NODE_KIND_METHOD_CALL | NODE_KIND_ARROW_EXPRESSION));
+            if parens > 1 || call {
+                return;
+            }
+
+            let mut cursor = condition.walk();
+            let Some(assignment) = condition.children(&mut cursor).find(|c| c.kind() == "=") else {
+                return;
+            };
+
+            let fix = Fix {
+                title: String::from("Compare with '=='"),
+                edits: vec![TextEdit::new(node_range(&assignment, ctx.source), String::from("=="))],
+            };
+            findings.push(
+                Finding::new(
+                    node_range(&condition, ctx.source),
+                    String::from("assignment in 'if' condition, did you mean '=='?"),
+                )
+                .with_fix(fix),
+            );
+        });
+    }
+}
+
+pub struct EmptyBlock;
+
+impl LintRule for EmptyBlock {
+    fn name(&self) -> &'static str {
+        "empty-block"
+    }
+
+    fn default_level(&self) -> Level {
+        Level::Info
+    }
+
+    /// Empty functions are fine, as they are often meant to be overloaded.
+    /// Blocks only containing a comment are considered intentionally empty.
+    fn check(&self, ctx: &LintContext, findings: &mut Vec<Finding>) {
+        visit(&ctx.tree.root_node(), &mut |block| {
+            let is_empty = block.kind() == NODE_KIND_BLOCK
+                && block.named_child_count() == 0
+                && block.parent().is_some_and(|p| p.kind() != NODE_KIND_FN_DEF);
+
+            if is_empty {
+                findings.push(Finding::new(node_range(block, ctx.source), String::from("empty block")));
+            }
+        });
+    }
+}
+
+pub struct ShadowedLocal;
+
+impl LintRule for ShadowedLocal {
+    fn name(&self) -> &'static str {
+        "shadowed-local"
+    }
+
+    fn default_level(&self) -> Level {
+        Level::Warning
+    }
+
+    fn check(&self, ctx: &LintContext, findings: &mut Vec<Finding>) {
+        for function in functions(ctx) {
+            let variables = FunctionVariables::collect(&function, ctx.source);
+
+            for (node, name) in variables.params.iter().chain(variables.vars.iter()) {
+                if let Some(scope) = ctx.script_variables.get(name) {
+                    findings.push(Finding::new(
+                        node_range(node, ctx.source),
+                        format!("'{}' shadows the {} variable of the same name", name, scope.moniker()),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+pub struct ForInNonArray;
+
+impl LintRule for ForInNonArray {
+    fn name(&self) -> &'static str {
+        "for-in-non-array"
+    }
+
+    fn default_level(&self) -> Level {
+        Level::Warning
+    }
+
+    fn check(&self, ctx: &LintContext, findings: &mut Vec<Finding>) {
+        visit(&ctx.tree.root_node(), &mut |node| {
+            if node.kind() != NODE_KIND_FOR_STATEMENT {
+                return;
+            }
+
+            let mut cursor = node.walk();
+            let collection = node
+                .children(&mut cursor)
+                .find(|c| c.kind() == "in")
+                .and_then(|c| c.next_named_sibling());
+
+            let Some(collection) = collection else {
+                return;
+            };
+
+            if let Some(found) = ctx.types.type_of(&collection) {
+                if !C4DataType::Array.accepts(found) {
+                    findings.push(Finding::new(
+                        node_range(&collection, ctx.source),
+                        format!("'for'-'in' over {}, expected array", found.moniker()),
+                    ));
+                }
+            }
+        });
+    }
+}
+
+/// Parameters and `var`s of a function, with the names of all variables used in it
+struct FunctionVariables<'t> {
+    params: Vec<(Node<'t>, &'t str)>,
+    /// First declaration of every `var`
+    vars: Vec<(Node<'t>, &'t str)>,
+    used: HashSet<&'t str>,
+    /// Whether the parameters are accessed by `Par()` or forwarded with `...`
+    forwards: bool,
+}
+
+impl<'t> FunctionVariables<'t> {
+    fn collect(function: &Node<'t>, source: &'t [u8]) -> Self {
+        let mut variables = FunctionVariables {
+            params: vec![],
+            vars: vec![],
+            used: HashSet::new(),
+            forwards: false,
+        };
+
+        variables.collect_node(function, source);
+        variables
+    }
+
+    fn collect_node(&mut self, node: &Node<'t>, source: &'t [u8]) {
+        let mut cursor = node.walk();
+        for child in node.children(&mut cursor) {
+            self.forwards |= forwards_params(&child, source);
+            match child.kind() {
+                NODE_KIND_IDENTIFIER => self.add_identifier(&child, node, source),
+                _ => self.collect_node(&child, source),
+            }
+        }
+    }
+
+    fn add_identifier(&mut self, identifier: &Node<'t>, parent: &Node<'t>, source: &'t [u8]) {
+        if !is_variable(identifier, parent) {
+            return;
+        }
+
+        let Ok(name) = identifier.utf8_text(source) else {
+            return;
+        };
+
+        if !is_declaration(identifier, parent) {
+            self.used.insert(name);
+            return;
+        }
+
+        let declared = |list: &Vec<(Node, &str)>| list.iter().any(|(_, n)| *n == name);
+        match parent.kind() {
+            NODE_KIND_PARAM => self.params.push((*identifier, name)),
+            NODE_KIND_VAR_ASSIGNMENT
+                if parent.parent().is_some_and(|d| var_scope_of(&d, source) != Some("var")) => {}
+            _ if declared(&self.params) || declared(&self.vars) => {}
+            _ => self.vars.push((*identifier, name)),
+        }
+    }
+}
+
+/// Functions of the script without syntax errors, which would confuse the rules
+fn functions<'t>(ctx: &LintContext<'t>) -> Vec<Node<'t>> {
+    let root = ctx.tree.root_node();
+    let mut cursor = root.walk();
+    let functions = root
+        .named_children(&mut cursor)
+        .filter(|n| n.kind() == NODE_KIND_FN_DEF && !n.has_error())
+        .collect();
+    functions
+}
+
+/// Calls `f` for the node and all of its named descendants.
+fn visit<'t>(node: &Node<'t>, f: &mut dyn FnMut(&Node<'t>)) {
+    f(node);
+
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        visit(&child, f);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::collections::HashMap;
+
+    use tower_lsp::lsp_types::Position;
+
+    use super::*;
+    use crate::{
+        core::{kind::NODE_KIND_ARRAY, lint::ExpressionTypes, signatures::VarScope},
+        lsp::doc::DocType,
+    };
+
+    /// Types of literals only, which is enough for the rules
+    struct LiteralTypes;
+
+    impl ExpressionTypes for LiteralTypes {
+        fn type_of(&self, node: &Node) -> Option<C4DataType> {
+            match node.kind() {
+                "number" => Some(C4DataType::Int),
+                "string" => Some(C4DataType::String),
+                NODE_KIND_ARRAY => Some(C4DataType::Array),
+                _ => None,
+            }
+        }
+    }
+
+    fn check(rule: &dyn LintRule, source: &str) -> Vec<Finding> {
+        let tree = DocType::Script.get_parser().unwrap().parse(source, None).unwrap();
+        let ctx = LintContext {
+            tree: &tree,
+            source: source.as_bytes(),
+            script_variables: HashMap::from([("count", VarScope::Local)]),
+            types: &LiteralTypes,
+        };
+
+        let mut findings = vec![];
+        rule.check(&ctx, &mut findings);
+        findings
+    }
+
+    fn messages(rule: &dyn LintRule, source: &str) -> Vec<String> {
+        check(rule, source).into_iter().map(|f| f.message).collect()
+    }
+
+    #[test]
+    fn should_find_unused_variables() {
+        let source = "func Foo(a, key, c) {\n  var x = a, y;\n  for (var item in [1]) y = item;\n  var x;\n  \
+                      return {key = y}.key;\n}\nfunc Bar(a) { return Par(0); }\n";
+
+        assert_eq!(messages(&UnusedParam, source), vec!["parameter 'key' is never used", "parameter 'c' is never used"]);
+        assert_eq!(messages(&UnusedVar, source), vec!["variable 'x' is never used"]);
+        assert!(check(&UnusedVar, source)[0].unnecessary);
+    }
+
+    #[test]
+    fn should_find_suspicious_control_flow() {
+        let source = "func Foo(a) {\n  if (a = 1) { return 1; Log(\"x\"); }\n  while (a) { /* wait */ }\n  for (;;) {}\n  \
+                      return 0;\n  // done\n}\nfunc Bar() {}\n";
+
+        let unreachable = check(&UnreachableCode, source);
+        assert_eq!(unreachable.len(), 1);
+        assert_eq!(unreachable[0].range, Range::new(Position::new(1, 25), Position::new(1, 34)));
+        let edit = &unreachable[0].fix.as_ref().unwrap().edits[0];
+        assert_eq!(edit.range.start, Position::new(1, 24));
+
+        let assignment = check(&AssignmentInIf, source);
+        assert_eq!(assignment[0].message, "assignment in 'if' condition, did you mean '=='?");
+        let edit = &assignment[0].fix.as_ref().unwrap().edits[0];
+        assert_eq!((edit.range.start, edit.new_text.as_str()), (Position::new(1, 8), "=="));
+
+        let intended = "func Foo(a) {\n  if (a = FindObject(ROCK)) {}\n  if (a = a->Contents()) {}\n  if ((a = 1)) {}\n}\n";
+        assert!(check(&AssignmentInIf, intended).is_empty());
+
+        let empty = check(&EmptyBlock, source);
+        assert_eq!(empty.len(), 1);
+        assert_eq!(empty[0].range.start, Position::new(3, 11));
+    }
+
+    #[test]
+    fn should_find_shadowing_and_bad_iteration() {
+        let source = "func Foo(count) {\n  for (var x in \"abc\") {}\n  for (var y in [1]) {}\n  for (var z in count) {}\n}\n";
+
+        assert_eq!(messages(&ShadowedLocal, source), vec!["'count' shadows the local variable of the same name"]);
+        assert_eq!(messages(&ForInNonArray, source), vec!["'for'-'in' over string, expected array"]);
+    }
+}
@@ -3,3 +3,7 @@ pub mod parse;
 pub mod project;
 pub mod kind;
 pub mod embedding;
+pub mod fuzzy;
+pub mod ini;
+pub mod definitions;
+pub mod lint;
@@ -2,19 +2,44 @@ use std::{fs, path::PathBuf, str::FromStr};
 
 use anyhow::{Context, Ok};
 use serde::{Deserialize, Serialize};
-use tree_sitter::{Parser, Tree};
+use tower_lsp::lsp_types::{Position, Range};
+use tree_sitter::{Node, Parser, Point, Tree};
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+use super::kind::{
+    NODE_KIND_ARGS_LIST, NODE_KIND_FN_DEF, NODE_KIND_FOR_STATEMENT, NODE_KIND_MAP_ACCESS, NODE_KIND_MAP_KEY, NODE_KIND_METHOD_CALL,
+    NODE_KIND_PARAM, NODE_KIND_VAR_ASSIGNMENT, NODE_KIND_VAR_DEFINITION, NODE_KIND_VAR_DEFINITION_INLINE,
+    NODE_KIND_VAR_SCOPE,
+};
+
+/// Words of the language, which cannot be used as names
+pub const KEYWORDS: &[&str] = &[
+    "func", "return", "if", "else", "while", "for", "in", "var", "local", "static", "const", "break",
+    "continue", "true", "false", "nil", "this", "private", "protected", "public", "global", "eq", "ne",
+];
+
+/// Engine function, which accesses parameters by their index
+const PAR_FUNCTION: &str = "Par";
+
+/// Forwards all parameters of the calling function, e.g. `_inherited(...)`
+pub const FORWARD_ARGS: &str = "...";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct FileId {
     pub path: Box<PathBuf>,
 }
 
 impl FileId {
-    pub fn from_path(s: &String) -> anyhow::Result<Self> {
+    pub fn from_path(s: &str) -> anyhow::Result<Self> {
         Ok(FileId {
             path: Box::new(PathBuf::from_str(s)?),
         })
     }
+
+    pub fn from_path_buf(path: PathBuf) -> Self {
+        FileId {
+            path: Box::new(path),
+        }
+    }
 }
 
 pub fn parse_file(file_id: &FileId) -> anyhow::Result<(Tree, Vec<u8>)> {
@@ -32,3 +57,86 @@ pub fn parse_file(file_id: &FileId) -> anyhow::Result<(Tree, Vec<u8>)> {
         .map(|v| (v, content))
 }
 
+
+/// Converts a tree-sitter point (byte based column) into a lsp
+/// position (utf-16 based column) by looking at the line's source.
+pub fn point_to_position(point: Point, byte: usize, source: &[u8]) -> Position {
+    let line_start = byte.saturating_sub(point.column);
+    let prefix = String::from_utf8_lossy(&source[line_start..byte]);
+
+    Position {
+        line: point.row as u32,
+        character: prefix.encode_utf16().count() as u32,
+    }
+}
+
+pub fn node_range(node: &Node, source: &[u8]) -> Range {
+    Range {
+        start: point_to_position(node.start_position(), node.start_byte(), source),
+        end: point_to_position(node.end_position(), node.end_byte(), source),
+    }
+}
+
+/// Whether the identifier refers to a variable. Function names, parameter types,
+/// map keys and property accesses (`map.key`) are no variables.
+pub fn is_variable(node: &Node, parent: &Node) -> bool {
+    match parent.kind() {
+        NODE_KIND_FN_DEF | NODE_KIND_METHOD_CALL => parent.child_by_field_name("name") != Some(*node),
+        NODE_KIND_PARAM => parent.child_by_field_name("name") == Some(*node),
+        NODE_KIND_MAP_KEY => false,
+        NODE_KIND_MAP_ACCESS => node.prev_sibling().is_none_or(|s| s.kind() != "."),
+        _ => true,
+    }
+}
+
+pub fn is_declaration(node: &Node, parent: &Node) -> bool {
+    match parent.kind() {
+        NODE_KIND_PARAM => true,
+        NODE_KIND_VAR_ASSIGNMENT => parent.child(0) == Some(*node),
+        NODE_KIND_FOR_STATEMENT => node.prev_sibling().is_some_and(|s| s.kind() == "var"),
+        _ => false,
+    }
+}
+
+pub fn var_scope_of<'a>(definition: &Node, source: &'a [u8]) -> Option<&'a str> {
+    if !matches!(definition.kind(), NODE_KIND_VAR_DEFINITION | NODE_KIND_VAR_DEFINITION_INLINE) {
+        return None;
+    }
+
+    definition
+        .child(0)
+        .filter(|c| c.kind() == NODE_KIND_VAR_SCOPE)
+        .and_then(|c| c.utf8_text(source).ok())
+}
+
+/// Operator of a unary or binary expression, ignoring parentheses around operands
+pub fn operator<'t>(node: &Node<'t>) -> Option<&'t str> {
+    let mut cursor = node.walk();
+    let op = node
+        .children(&mut cursor)
+        .find(|c| !c.is_named() && !matches!(c.kind(), "(" | ")"));
+    op.map(|o| o.kind())
+}
+
+/// Whether the node accesses the parameters of its function by `Par()`
+/// or forwards them with `...`, so the function takes any number of arguments
+pub fn forwards_params(node: &Node, source: &[u8]) -> bool {
+    match node.kind() {
+        FORWARD_ARGS => node.parent().is_some_and(|p| p.kind() == NODE_KIND_ARGS_LIST),
+        NODE_KIND_METHOD_CALL => {
+            node.child_by_field_name("name").and_then(|n| n.utf8_text(source).ok()) == Some(PAR_FUNCTION)
+        }
+        _ => false,
+    }
+}
+
+/// Whether any node of the function forwards its parameters, see [`forwards_params`]
+pub fn is_variadic(function: &Node, source: &[u8]) -> bool {
+    if forwards_params(function, source) {
+        return true;
+    }
+
+    let mut cursor = function.walk();
+    let variadic = function.children(&mut cursor).any(|c| is_variadic(&c, source));
+    variadic
+}
@@ -1,6 +1,575 @@
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+};
 
+use super::{
+    definitions::{DefinitionInfo, DEF_CORE_FILE_NAME, NAMES_FILE_NAME},
+    lint::{LintConfig, CONFIG_FILE_NAME},
+    parse::{parse_file, FileId},
+    signatures::{FileSignatures, Signature, SignatureCollector},
+};
+
+/// Folder extensions, which contain scripts we want to index
+/// (definitions, scenarios and folders).
+const INDEXED_FOLDER_EXTENSIONS: [&str; 3] = ["c4d", "c4s", "c4f"];
+const SCRIPT_EXTENSION: &str = "c";
+const MATERIAL_EXTENSION: &str = "c4m";
+const APPENDTO_ALL: &str = "*";
+
+pub type FunctionMatch<'a> = (&'a FileSignatures, &'a Signature);
+
+/// Workspace wide index of all scripts and their signatures.
 #[derive(Debug, Default)]
-pub struct Project;
+pub struct Project {
+    roots: Vec<PathBuf>,
+    files: HashMap<FileId, FileSignatures>,
+    /// Names of the materials defined in the workspace
+    materials: BTreeSet<String>,
+    /// Definitions of the workspace by their id
+    definitions: HashMap<String, DefinitionInfo>,
+    /// Lint levels of the `initializationOptions`
+    lint_options: LintConfig,
+    /// Lint levels including the configuration files of the workspace roots
+    lint_config: LintConfig,
+}
 
 impl Project {
+    pub fn new(roots: Vec<PathBuf>) -> Self {
+        Project {
+            roots,
+            files: HashMap::new(),
+            materials: BTreeSet::new(),
+            definitions: HashMap::new(),
+            lint_options: LintConfig::default(),
+            lint_config: LintConfig::default(),
+        }
+    }
+
+    /// Project with the same roots and options, which has not been indexed yet.
+    /// Allows to index the workspace without locking the current project.
+    pub fn unindexed(&self) -> Self {
+        Project {
+            lint_options: self.lint_options.clone(),
+            ..Project::new(self.roots.clone())
+        }
+    }
+
+    pub fn roots(&self) -> &[PathBuf] {
+        &self.roots
+    }
+
+    pub fn set_roots(&mut self, roots: Vec<PathBuf>) {
+        self.roots = roots;
+    }
+
+    pub fn lint_config(&self) -> &LintConfig {
+        &self.lint_config
+    }
+
+    pub fn set_lint_options(&mut self, options: LintConfig) {
+        self.lint_options = options;
+        self.reload_lint_config();
+    }
+
+    pub fn files(&self) -> impl Iterator<Item = &FileSignatures> {
+        self.files.values()
+    }
+
+    pub fn materials(&self) -> impl Iterator<Item = &String> {
+        self.materials.iter()
+    }
+
+    pub fn definition(&self, id: &str) -> Option<&DefinitionInfo> {
+        self.definitions.get(id)
+    }
+
+    pub fn definitions(&self) -> impl Iterator<Item = &DefinitionInfo> {
+        self.definitions.values()
+    }
+
+    pub fn get(&self, path: &Path) -> Option<&FileSignatures> {
+        self.files.get(&FileId::from_path_buf(path.to_path_buf()))
+    }
+
+    /// Crawls all workspace roots and (re-)indexes every script
+    /// inside a definition, scenario or folder, every material and every definition.
+    /// Returns the number of indexed scripts.
+    pub fn index_roots(&mut self) -> usize {
+        self.files.clear();
+        self.materials.clear();
+        self.definitions.clear();
+        self.reload_lint_config();
+
+        let mut scripts = vec![];
+        for root in self.roots.iter() {
+            collect_scripts(
+                root,
+                is_indexed_folder(root),
+                &mut scripts,
+                &mut self.materials,
+                &mut self.definitions,
+            );
+        }
+
+        for path in scripts {
+            self.update_file(&path);
+        }
+
+        self.files.len()
+    }
+
+    /// Re-parses a single script and replaces its signatures.
+    /// Files outside of indexed folders are ignored.
+    pub fn update_file(&mut self, path: &Path) {
+        if self.is_lint_config_file(path) {
+            self.reload_lint_config();
+            return;
+        }
+
+        if let Some(name) = material_name(path) {
+            self.materials.insert(name);
+            return;
+        }
+
+        if is_definition_file(path) {
+            if let Some(folder) = path.parent() {
+                self.update_definition(folder);
+            }
+            return;
+        }
+
+        if !Self::is_indexed_script(path) {
+            return;
+        }
+
+        let file_id = FileId::from_path_buf(path.to_path_buf());
+        match Self::collect_file(file_id.clone()) {
+            Ok(mut sigs) => {
+                sigs.definition_id = path.parent().and_then(read_definition_id);
+                self.files.insert(file_id, sigs);
+            }
+            Err(e) => {
+                tracing::error!("Could not index file {}: {}", path.display(), e);
+                self.files.remove(&file_id);
+            }
+        }
+    }
+
+    pub fn remove_file(&mut self, path: &Path) {
+        if self.is_lint_config_file(path) {
+            self.reload_lint_config();
+            return;
+        }
+
+        if let Some(name) = material_name(path) {
+            self.materials.remove(&name);
+            return;
+        }
+
+        if is_definition_file(path) {
+            if let Some(folder) = path.parent() {
+                // without its DefCore.txt, the folder is no definition anymore
+                self.update_definition(folder);
+            }
+            return;
+        }
+
+        self.files.remove(&FileId::from_path_buf(path.to_path_buf()));
+    }
+
+    /// Combines the `initializationOptions` with the configuration files of the
+    /// workspace roots. Invalid files are logged and ignored.
+    fn reload_lint_config(&mut self) {
+        let mut config = self.lint_options.clone();
+
+        for path in self.roots.iter().map(|root| root.join(CONFIG_FILE_NAME)) {
+            let Ok(text) = fs::read_to_string(&path) else {
+                continue;
+            };
+
+            match LintConfig::from_toml(&text) {
+                Ok(file_config) => config.merge(file_config),
+                Err(e) => tracing::error!("Could not read {}: {}", path.display(), e),
+            }
+        }
+
+        self.lint_config = config;
+    }
+
+    /// Whether the file is the lint configuration of a workspace root
+    pub fn is_lint_config_file(&self, path: &Path) -> bool {
+        path.file_name().is_some_and(|name| name == CONFIG_FILE_NAME)
+            && path.parent().is_some_and(|dir| self.roots.iter().any(|root| root == dir))
+    }
+
+    /// Re-reads the definition in the folder and updates the id of its scripts.
+    fn update_definition(&mut self, folder: &Path) {
+        self.definitions.retain(|_, def| def.folder != folder);
+
+        let info = DefinitionInfo::read(folder);
+        let id = info.as_ref().map(|i| i.id.clone());
+
+        for sigs in self.files.values_mut() {
+            if sigs.file_id.path.parent() == Some(folder) {
+                sigs.definition_id = id.clone();
+            }
+        }
+
+        if let Some(info) = info {
+            self.definitions.insert(info.id.clone(), info);
+        }
+    }
+
+    /// Whether the path is a script inside a definition, scenario or folder.
+    pub fn is_indexed_script(path: &Path) -> bool {
+        path.extension().is_some_and(|ext| ext == SCRIPT_EXTENSION)
+            && path.ancestors().skip(1).any(is_indexed_folder)
+    }
+
+    /// Id of the definition the script belongs to, taken from the
+    /// index if possible and from the sibling DefCore.txt otherwise.
+    pub fn definition_id_for(&self, path: &Path) -> Option<String> {
+        match self.get(path) {
+            Some(sigs) => sigs.definition_id.clone(),
+            None => path.parent().and_then(read_definition_id),
+        }
+    }
+
+    /// All scripts contributing to a definition: its own script
+    /// and all scripts appending to it.
+    pub fn definition_scripts(&self, id: &str) -> Vec<&FileSignatures> {
+        self.files
+            .values()
+            .filter(|f| {
+                f.definition_id.as_deref() == Some(id)
+                    || f.appendto.as_deref().is_some_and(|a| a == id || a == APPENDTO_ALL)
+            })
+            .collect()
+    }
+
+    /// Resolves a function of a definition, e.g. for `CLNK::DoStuff()`.
+    /// Scripts of the definition (including `#appendto`s) take precedence
+    /// over the `#include`d definitions.
+    pub fn find_function(&self, id: &str, name: &str) -> Vec<FunctionMatch<'_>> {
+        let mut visited = HashSet::from([id.to_string()]);
+        self.resolve_function(self.definition_scripts(id), name, &mut visited)
+    }
+
+    /// Resolves an unqualified call inside of `script`.
+    /// The script itself may not be part of the index (e.g. an open document
+    /// with unsaved changes), so it is searched before the indexed scripts
+    /// of its definition. Global functions are used as fallback.
+    pub fn find_function_for_script<'a>(
+        &'a self,
+        script: &'a FileSignatures,
+        name: &str,
+    ) -> Vec<FunctionMatch<'a>> {
+        let mut visited = HashSet::new();
+        let scripts = self.own_scripts(script, &mut visited);
+
+        let matches = self.resolve_function(scripts, name, &mut visited);
+
+        if matches.is_empty() {
+            self.find_global_function(name)
+        } else {
+            matches
+        }
+    }
+
+    /// All scripts whose functions and locals are available inside
+    /// of `script`: the script itself, the other scripts of its definition
+    /// and all (transitively) included definitions.
+    pub fn script_chain<'a>(&'a self, script: &'a FileSignatures) -> Vec<&'a FileSignatures> {
+        let mut visited = HashSet::new();
+        let scripts = self.own_scripts(script, &mut visited);
+
+        self.collect_includes(scripts, &mut visited)
+    }
+
+    /// Like [`Project::script_chain`], but for all scripts of a definition.
+    pub fn definition_chain(&self, id: &str) -> Vec<&FileSignatures> {
+        let mut visited = HashSet::from([id.to_string()]);
+        self.collect_includes(self.definition_scripts(id), &mut visited)
+    }
+
+    /// The script itself and the other scripts of its definition
+    /// (or the definition it appends to).
+    fn own_scripts<'a>(
+        &'a self,
+        script: &'a FileSignatures,
+        visited: &mut HashSet<String>,
+    ) -> Vec<&'a FileSignatures> {
+        let mut scripts = vec![script];
+
+        let ids = script
+            .definition_id
+            .iter()
+            .chain(script.appendto.iter().filter(|a| *a != APPENDTO_ALL));
+
+        for id in ids {
+            visited.insert(id.clone());
+            scripts.extend(
+                self.definition_scripts(id)
+                    .into_iter()
+                    .filter(|f| f.file_id != script.file_id),
+            );
+        }
+
+        scripts
+    }
+
+    fn collect_includes<'a>(
+        &'a self,
+        mut scripts: Vec<&'a FileSignatures>,
+        visited: &mut HashSet<String>,
+    ) -> Vec<&'a FileSignatures> {
+        let mut idx = 0;
+        while idx < scripts.len() {
+            let included_ids: Vec<&String> = scripts[idx]
+                .includes
+                .iter()
+                .filter(|id| visited.insert(id.to_string()))
+                .collect();
+
+            for id in included_ids {
+                scripts.extend(self.definition_scripts(id));
+            }
+
+            idx += 1;
+        }
+
+        scripts
+    }
+
+    pub fn find_global_function(&self, name: &str) -> Vec<FunctionMatch<'_>> {
+        self.find_function_anywhere(name)
+            .into_iter()
+            .filter(|(_, sig)| sig.is_global())
+            .collect()
+    }
+
+    /// Every function with the given name, regardless of its definition.
+    /// Used for calls on objects of unknown type, e.g. `obj->~Activate()`.
+    pub fn find_function_anywhere(&self, name: &str) -> Vec<FunctionMatch<'_>> {
+        self.files
+            .values()
+            .filter_map(|f| f.get_signature(name).map(|sig| (f, sig)))
+            .collect()
+    }
+
+    fn resolve_function<'a>(
+        &'a self,
+        scripts: Vec<&'a FileSignatures>,
+        name: &str,
+        visited: &mut HashSet<String>,
+    ) -> Vec<FunctionMatch<'a>> {
+        let matches: Vec<FunctionMatch> = scripts
+            .iter()
+            .filter_map(|f| f.get_signature(name).map(|sig| (*f, sig)))
+            .collect();
+
+        if !matches.is_empty() {
+            return matches;
+        }
+
+        let included_ids: Vec<&String> = scripts
+            .iter()
+            .flat_map(|f| f.includes.iter())
+            .filter(|id| visited.insert(id.to_string()))
+            .collect();
+
+        let included: Vec<&FileSignatures> = included_ids
+            .into_iter()
+            .flat_map(|id| self.definition_scripts(id))
+            .collect();
+
+        if included.is_empty() {
+            vec![]
+        } else {
+            self.resolve_function(included, name, visited)
+        }
+    }
+
+    /// Parses a script from disk, without adding it to the index.
+    pub fn collect_file(file_id: FileId) -> anyhow::Result<FileSignatures> {
+        let (tree, content) = parse_file(&file_id)?;
+        SignatureCollector::collect(file_id, &tree, content)
+    }
+}
+
+/// Reads the `id` of the `[DefCore]` section of the definition folder.
+fn read_definition_id(folder: &Path) -> Option<String> {
+    DefinitionInfo::read(folder).map(|info| info.id)
+}
+
+/// `DefCore.txt` and `Names.txt`, which describe a definition
+fn is_definition_file(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| name == DEF_CORE_FILE_NAME || name == NAMES_FILE_NAME)
+}
+
+fn is_indexed_folder(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| INDEXED_FOLDER_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+}
+
+/// Materials are named after their file, e.g. `Earth.c4m`.
+fn material_name(path: &Path) -> Option<String> {
+    if !path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case(MATERIAL_EXTENSION)) {
+        return None;
+    }
+
+    path.file_stem().and_then(|s| s.to_str()).map(String::from)
+}
+
+fn collect_scripts(
+    dir: &Path,
+    inside_indexed_folder: bool,
+    scripts: &mut Vec<PathBuf>,
+    materials: &mut BTreeSet<String>,
+    definitions: &mut HashMap<String, DefinitionInfo>,
+) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            tracing::error!("Could not read directory {}: {}", dir.display(), e);
+            return;
+        }
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+
+        if file_type.is_dir() {
+            let inside = inside_indexed_folder || is_indexed_folder(&path);
+            collect_scripts(&path, inside, scripts, materials, definitions);
+        } else if let Some(name) = material_name(&path) {
+            materials.insert(name);
+        } else if path.file_name().is_some_and(|name| name == DEF_CORE_FILE_NAME) {
+            if let Some(info) = DefinitionInfo::read(dir) {
+                definitions.insert(info.id.clone(), info);
+            }
+        } else if inside_indexed_folder
+            && path.extension().is_some_and(|ext| ext == SCRIPT_EXTENSION)
+        {
+            scripts.push(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::core::lint::{self, Level};
+
+    fn create_definition(root: &Path, folder: &str, id: &str, script: &str) {
+        let def = root.join(folder);
+        fs::create_dir_all(&def).unwrap();
+        fs::write(def.join("DefCore.txt"), format!("[DefCore]\nid={}\nName=Test\n", id)).unwrap();
+        fs::write(def.join("Script.c"), script).unwrap();
+    }
+
+    #[test]
+    fn should_only_index_scripts_in_definition_folders() {
+        assert!(Project::is_indexed_script(Path::new("/ws/Objects.c4d/Clonk.c4d/Script.c")));
+        assert!(Project::is_indexed_script(Path::new("/ws/Foo.c4s/System.c4g/Rules.c")));
+        assert!(!Project::is_indexed_script(Path::new("/ws/tools/Script.c")));
+        assert!(!Project::is_indexed_script(Path::new("/ws/Foo.c4d/DefCore.txt")));
+    }
+
+    #[test]
+    fn should_index_scripts_of_workspace() {
+        let root = std::env::temp_dir().join(format!("c4-project-test-{}", std::process::id()));
+        let def = root.join("Objects.c4d").join("Clonk.c4d");
+        fs::create_dir_all(&def).unwrap();
+        fs::create_dir_all(root.join("other")).unwrap();
+        fs::write(def.join("Script.c"), "func Foo(a, int b) {}\nfunc Bar() {}").unwrap();
+        fs::write(root.join("other").join("Script.c"), "func Baz() {}").unwrap();
+        fs::create_dir_all(root.join("Material.c4g")).unwrap();
+        fs::write(root.join("Material.c4g").join("Mud.c4m"), "[Material]\nName=Mud\n").unwrap();
+
+        let mut project = Project::new(vec![root.clone()]);
+        assert_eq!(project.index_roots(), 1);
+        assert_eq!(project.materials().collect::<Vec<&String>>(), vec!["Mud"]);
+
+        let sigs = project.get(&def.join("Script.c")).unwrap();
+        assert_eq!(sigs.signatures.len(), 2);
+        assert_eq!(sigs.signatures[0].params.len(), 2);
+
+        fs::write(def.join("Script.c"), "func Foo() {}").unwrap();
+        project.update_file(&def.join("Script.c"));
+        assert_eq!(project.get(&def.join("Script.c")).unwrap().signatures.len(), 1);
+
+        project.remove_file(&def.join("Script.c"));
+        assert_eq!(project.files().count(), 0);
+
+        project.remove_file(&root.join("Material.c4g").join("Mud.c4m"));
+        assert_eq!(project.materials().count(), 0);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn should_combine_lint_config_of_options_and_workspace() {
+        let root = std::env::temp_dir().join(format!("c4-lint-config-test-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        let unused_param = lint::rule("unused-param").unwrap();
+        let empty_block = lint::rule("empty-block").unwrap();
+
+        let mut project = Project::new(vec![root.clone()]);
+        project.set_lint_options(LintConfig::from_toml("[lint]\nunused-param = \"off\"\nempty-block = \"off\"\n").unwrap());
+        assert_eq!(project.lint_config().level(empty_block), Level::Off);
+
+        let file = root.join(CONFIG_FILE_NAME);
+        fs::write(&file, "[lint]\nempty-block = \"error\"\n").unwrap();
+        project.update_file(&file);
+        assert_eq!(project.lint_config().level(unused_param), Level::Off);
+        assert_eq!(project.lint_config().level(empty_block), Level::Error);
+
+        fs::remove_file(&file).unwrap();
+        project.remove_file(&file);
+        assert_eq!(project.lint_config().level(empty_block), Level::Off);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn should_resolve_functions_through_include_and_appendto() {
+        let root = std::env::temp_dir().join(format!("c4-resolve-test-{}", std::process::id()));
+        create_definition(&root, "Base.c4d", "BASE", "func Foo() {}\nfunc Bar() {}");
+        create_definition(&root, "Clonk.c4d", "CLNK", "#include BASE\nfunc Bar() {}");
+        create_definition(&root, "Ext.c4d", "EXTX", "#appendto CLNK\nglobal func Baz() {}");
+
+        let mut project = Project::new(vec![root.clone()]);
+        project.index_roots();
+
+        let clonk_script = root.join("Clonk.c4d").join("Script.c");
+        assert_eq!(project.definition_id_for(&clonk_script).as_deref(), Some("CLNK"));
+        assert_eq!(project.definition("CLNK").unwrap().folder, root.join("Clonk.c4d"));
+        assert_eq!(project.definitions().count(), 3);
+
+        let foo = project.find_function("CLNK", "Foo");
+        assert_eq!(foo.len(), 1);
+        assert_eq!(foo[0].0.definition_id.as_deref(), Some("BASE"));
+
+        // the definition's own function shadows the included one
+        let bar = project.find_function("CLNK", "Bar");
+        assert_eq!(bar.len(), 1);
+        assert_eq!(bar[0].0.definition_id.as_deref(), Some("CLNK"));
+
+        assert_eq!(project.find_function("CLNK", "Baz").len(), 1);
+        assert!(project.find_function("BASE", "Baz").is_empty());
+
+        let base_script = project.get(&root.join("Base.c4d").join("Script.c")).unwrap();
+        assert_eq!(project.find_function_for_script(base_script, "Baz").len(), 1);
+
+        fs::remove_dir_all(root).unwrap();
+    }
 }
@@ -1,15 +1,20 @@
 use anyhow::bail;
 use serde::{Deserialize, Serialize};
-use tree_sitter::Tree;
+use tower_lsp::lsp_types::Range;
+use tree_sitter::{Node, Tree};
 use crate::core::kind::NODE_KIND_SOURCE_FILE;
 
-use super::{kind::NODE_KIND_FN_DEF, parse::FileId};
-
-const DEBUG_WALK: bool = true;
+use super::{
+    kind::{
+        NODE_KIND_APPENDTO, NODE_KIND_COMMENT, NODE_KIND_FN_DEF, NODE_KIND_FUNC_DESC, NODE_KIND_INCLUDE,
+        NODE_KIND_PRAGMA_STRICT, NODE_KIND_VAR_ASSIGNMENT, NODE_KIND_VAR_DEFINITION,
+    },
+    parse::{FileId, is_variadic, node_range},
+};
 
 pub struct SignatureCollector;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
 pub enum C4DataType {
     #[serde(rename(deserialize = "int"))]
     Int,
@@ -23,6 +28,8 @@ pub enum C4DataType {
     Object,
     #[serde(rename(deserialize = "array"))]
     Array,
+    #[serde(rename(deserialize = "map"))]
+    Map,
     #[serde(rename(deserialize = "any"))]
     Any,
 }
@@ -35,33 +42,221 @@ impl C4DataType {
             C4DataType::Bool => "bool",
             C4DataType::Any => "any",
             C4DataType::Array => "array",
+            C4DataType::Map => "map",
             C4DataType::Object => "object",
             C4DataType::String => "string",
         }
     }
+
+    /// Type of a parameter as written in a script, e.g. `int` in `func Foo(int a)`
+    pub fn from_moniker(moniker: &str) -> Option<Self> {
+        let data_type = match moniker {
+            "int" => C4DataType::Int,
+            "id" => C4DataType::Id,
+            "bool" => C4DataType::Bool,
+            "any" => C4DataType::Any,
+            "array" => C4DataType::Array,
+            "map" => C4DataType::Map,
+            "object" => C4DataType::Object,
+            "string" => C4DataType::String,
+            _ => return None,
+        };
+
+        Some(data_type)
+    }
+
+    /// Whether a value of type `other` may be passed where `self` is expected.
+    /// `int` and `bool` convert into each other.
+    pub fn accepts(&self, other: C4DataType) -> bool {
+        matches!(
+            (self, other),
+            (C4DataType::Any, _) | (_, C4DataType::Any) | (C4DataType::Int, C4DataType::Bool) | (C4DataType::Bool, C4DataType::Int)
+        ) || *self == other
+    }
 }
 
 impl std::fmt::Display for C4DataType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
+        write!(f, "{}", self.moniker())
     }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Param {
     pub name: String,
+    /// Type as written in the script, e.g. `int`
+    #[serde(default)]
+    pub data_type: Option<String>,
+    /// Passed by reference, e.g. `object &obj`
+    #[serde(default)]
+    pub is_ref: bool,
+}
+
+impl Param {
+    /// Parameter as written in the script, e.g. `int count` or `object &obj`
+    pub fn to_label(&self) -> String {
+        let name = if self.is_ref { format!("&{}", self.name) } else { self.name.clone() };
+        match self.data_type {
+            Some(ref t) => format!("{} {}", t, name),
+            None => name,
+        }
+    }
 }
 
 #[derive(Serialize, Default, Deserialize, Clone, Debug)]
 pub struct Signature {
     pub name: String,
     pub params: Vec<Param>,
+    pub visibility: Option<String>,
+    /// Range of the function name
+    pub name_range: Range,
+    /// Range of the whole function definition
+    pub range: Range,
+    /// Comment block in front of the function or its `[...]` description
+    #[serde(default)]
+    pub doc: Option<String>,
+    /// Whether the function accesses its parameters by `Par()` or forwards them with `...`
+    #[serde(default)]
+    pub variadic: bool,
+}
+
+impl Signature {
+    pub fn is_global(&self) -> bool {
+        self.visibility.as_deref() == Some("global")
+    }
+
+    pub fn is_private(&self) -> bool {
+        self.visibility.as_deref() == Some("private")
+    }
+
+    /// Signature as written in the script, e.g. `public func Foo(a, b)`
+    pub fn to_signature(&self) -> String {
+        let params: Vec<String> = self.params.iter().map(Param::to_label).collect();
+        let visibility = self.visibility.as_ref().map(|v| format!("{} ", v)).unwrap_or_default();
+
+        format!("{}func {}({})", visibility, self.name, params.join(", "))
+    }
+}
+
+/*
+Semantics of the `#strict` levels, as far as the analysis is concerned:
+
+    level       parameter types   `S=`, `eq`, `ne`   `0` as "no object"
+    none        not checked       allowed            allowed
+    #strict     checked           allowed            allowed
+    #strict 2   checked           deprecated         allowed
+    #strict 3   checked           not allowed        not allowed, use `nil`
+*/
+
+/// Level of the `#strict` pragma of a script
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StrictMode {
+    #[default]
+    None,
+    Strict,
+    Strict2,
+    Strict3,
+}
+
+impl StrictMode {
+    /// Level of a `pragma_strict` node, e.g. `#strict 2`
+    pub fn from_pragma(pragma: &str) -> Self {
+        match pragma.trim_start_matches("#strict").trim() {
+            "" => StrictMode::Strict,
+            "2" => StrictMode::Strict2,
+            "3" => StrictMode::Strict3,
+            _ => StrictMode::None,
+        }
+    }
+
+    pub fn moniker(&self) -> &'static str {
+        match self {
+            StrictMode::None => "no #strict",
+            StrictMode::Strict => "#strict",
+            StrictMode::Strict2 => "#strict 2",
+            StrictMode::Strict3 => "#strict 3",
+        }
+    }
+
+    /// Without `#strict`, every parameter is implicitly `any`.
+    pub fn checks_types(&self) -> bool {
+        *self >= StrictMode::Strict
+    }
+
+    /// `S=`, `eq` and `ne` are deprecated in favour of `==` and `!=` from `#strict 2` on
+    pub fn deprecates_string_operators(&self) -> bool {
+        *self >= StrictMode::Strict2
+    }
+
+    pub fn forbids_string_operators(&self) -> bool {
+        *self >= StrictMode::Strict3
+    }
+
+    /// Whether `0` may still be passed as "no object" instead of `nil`
+    pub fn allows_zero_as_nil(&self) -> bool {
+        *self < StrictMode::Strict3
+    }
+
+    pub fn describe(&self) -> &'static str {
+        match self {
+            StrictMode::None => "Parameter types are not checked.",
+            StrictMode::Strict => "Parameter types are checked.",
+            StrictMode::Strict2 => "Parameter types are checked, `S=`, `eq` and `ne` are deprecated in favour of `==` and `!=`.",
+            StrictMode::Strict3 => "Parameter types are checked, `S=`, `eq` and `ne` are not allowed and `nil` has to be used instead of `0` for missing objects.",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VarScope {
+    #[serde(rename = "local")]
+    Local,
+    #[serde(rename = "static")]
+    Static,
+}
+
+impl VarScope {
+    pub fn moniker(&self) -> &'static str {
+        match self {
+            VarScope::Local => "local",
+            VarScope::Static => "static",
+        }
+    }
+}
+
+/// A `local` or `static` variable declared on script level
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Variable {
+    pub name: String,
+    pub scope: VarScope,
+    pub is_const: bool,
+    pub range: Range,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct FileSignatures {
     pub file_id: FileId,
     pub signatures: Vec<Signature>,
+    pub variables: Vec<Variable>,
+    /// Definition ids of all `#include` directives
+    pub includes: Vec<String>,
+    /// Definition id (or `*`) of the `#appendto` directive
+    pub appendto: Option<String>,
+    #[serde(default)]
+    pub strict: StrictMode,
+    /// Id of the definition the script belongs to.
+    /// Not known to the collector, but resolved by the project.
+    pub definition_id: Option<String>,
+}
+
+impl FileSignatures {
+    pub fn get_signature(&self, name: &str) -> Option<&Signature> {
+        self.signatures.iter().find(|s| s.name == name)
+    }
+
+    pub fn get_variable(&self, name: &str) -> Option<&Variable> {
+        self.variables.iter().find(|v| v.name == name)
+    }
 }
 
 impl SignatureCollector {
@@ -72,6 +267,11 @@ impl SignatureCollector {
         let mut sigs = FileSignatures {
             file_id,
             signatures: vec![],
+            variables: vec![],
+            includes: vec![],
+            appendto: None,
+            strict: StrictMode::None,
+            definition_id: None,
         };
 
         let mut cursor = tree.walk();
@@ -80,34 +280,91 @@ impl SignatureCollector {
         }
 
         if !cursor.goto_first_child() {
-            bail!("Expected node '{}' to have ", NODE_KIND_SOURCE_FILE);
+            // empty script
+            return Ok(sigs);
         }
 
         loop {
             let node = cursor.node();
 
-            if DEBUG_WALK {
-                println!("Current node: {}", node.kind());
-            }
+            tracing::trace!("Current node: {}", node.kind());
 
             match node.kind() {
+                NODE_KIND_PRAGMA_STRICT => {
+                    if let Ok(pragma) = node.utf8_text(source) {
+                        sigs.strict = StrictMode::from_pragma(pragma);
+                    }
+                },
+                NODE_KIND_INCLUDE => {
+                    if let Some(Ok(id)) = node.child(1).map(|n| n.utf8_text(source)) {
+                        sigs.includes.push(id.to_string());
+                    }
+                },
+                NODE_KIND_APPENDTO => {
+                    if let Some(Ok(id)) = node.child(1).map(|n| n.utf8_text(source)) {
+                        sigs.appendto = Some(id.to_string());
+                    }
+                },
+                NODE_KIND_VAR_DEFINITION => {
+                    let scope = match node.child(0).and_then(|n| n.utf8_text(source).ok()) {
+                        Some("local") => VarScope::Local,
+                        Some("static") => VarScope::Static,
+                        _ => {
+                            if !cursor.goto_next_sibling() {
+                                break;
+                            }
+                            continue;
+                        }
+                    };
+
+                    let is_const = node.child_by_field_name("const").is_some();
+                    let mut var_cursor = node.walk();
+                    let assignments = node
+                        .named_children(&mut var_cursor)
+                        .filter(|n| n.kind() == NODE_KIND_VAR_ASSIGNMENT);
+
+                    for assignment in assignments {
+                        if let Some(name) = assignment.child(0) {
+                            if let Ok(n) = name.utf8_text(source) {
+                                sigs.variables.push(Variable {
+                                    name: n.to_string(),
+                                    scope,
+                                    is_const,
+                                    range: node_range(&name, source),
+                                });
+                            }
+                        }
+                    }
+                },
                 NODE_KIND_FN_DEF => {
-                    let mut sig = Signature::default();
+                    let mut sig = Signature {
+                        visibility: node
+                            .child_by_field_name("visibility")
+                            .and_then(|v| v.utf8_text(source).ok())
+                            .map(String::from),
+                        range: node_range(&node, source),
+                        doc: leading_comment(&node, source).or_else(|| func_desc(&node, source)),
+                        variadic: is_variadic(&node, source),
+                        ..Signature::default()
+                    };
 
                     if let Some(ref name) = node.child_by_field_name("name") {
+                        sig.name_range = node_range(name, source);
                         match name.utf8_text(source) {
                             Ok(n) => sig.name = n.to_string(),
                             Err(e) => {
-                                if DEBUG_WALK {
-                                    println!("Could not parse name of function signature: {}", e);
-                                }
-                                continue;
+                                tracing::debug!("Could not parse name of function signature: {}", e);
                             },
                         }
-                    } else {
-                        // should never be reached, but without
-                        // a function name, there is nothing 
-                        // sound we can do here
+                    }
+
+                    // should never be the case, but without
+                    // a function name, there is nothing
+                    // sound we can do here
+                    if sig.name.is_empty() {
+                        if !cursor.goto_next_sibling() {
+                            break;
+                        }
                         continue;
                     }
                     // enter children of function_definition
@@ -116,9 +373,7 @@ impl SignatureCollector {
                         loop {
                             if cursor.node().kind() == "parameter_list" {
                                 let params = cursor.node();
-                                if DEBUG_WALK {
-                                    println!("Found parameter_list with {} children", params.child_count());
-                                }
+                                tracing::trace!("Found parameter_list with {} children", params.child_count());
 
                                 // enter children of parameter_list
                                 if cursor.goto_first_child() {
@@ -126,26 +381,13 @@ impl SignatureCollector {
                                         let child = cursor.node();
                                         if child.kind() == "param" {
                                             match child.child_count() {
-                                                1 => {
-                                                    let name_node = child.child(0).unwrap();
-                                                    let name = name_node.utf8_text(source).unwrap();
-                                                    let p = Param {
-                                                        name: name.to_string(),
-                                                    };
-
-                                                    sig.params.push(p);
-                                                },
-                                                2 => {
-                                                    let name_node = child.child(1).unwrap();
-                                                    let name = name_node.utf8_text(source).unwrap();
-                                                    let p = Param {
-                                                        name: name.to_string(),
-                                                    };
-
-                                                    sig.params.push(p);
+                                                1..=3 => {
+                                                    if let Some(p) = collect_param(&child, source) {
+                                                        sig.params.push(p);
+                                                    }
                                                 },
                                                 c => {
-                                                    println!("Unexpected child count of param node: {}", c);
+                                                    tracing::debug!("Unexpected child count of param node: {}", c);
                                                 },
                                             }
                                         }
@@ -182,3 +424,91 @@ impl SignatureCollector {
     }
 }
 
+/// Text of the comments directly in front of a node, without blank lines in between.
+fn leading_comment(node: &Node, source: &[u8]) -> Option<String> {
+    let mut comments = vec![];
+    let mut next_row = node.start_position().row;
+    let mut current = node.prev_sibling();
+
+    while let Some(prev) = current.filter(|p| p.kind() == NODE_KIND_COMMENT) {
+        if prev.end_position().row + 1 < next_row {
+            break;
+        }
+
+        comments.push(prev.utf8_text(source).ok()?);
+        next_row = prev.start_position().row;
+        current = prev.prev_sibling();
+    }
+
+    let lines: Vec<&str> = comments
+        .iter()
+        .rev()
+        .flat_map(|c| strip_comment(c).lines())
+        .map(|l| {
+            let l = l.trim();
+            l.strip_prefix("* ").or_else(|| l.strip_prefix('*')).unwrap_or(l)
+        })
+        .collect();
+
+    let text = lines.join("\n").trim().to_string();
+    (!text.is_empty()).then_some(text)
+}
+
+fn strip_comment(comment: &str) -> &str {
+    match comment.strip_prefix("//") {
+        Some(line) => line,
+        None => comment
+            .trim_start_matches("/*")
+            .trim_start_matches('*')
+            .trim_end_matches("*/"),
+    }
+}
+
+/// Description of the function in its first statement, e.g. `[Opens the door]`
+fn func_desc(function: &Node, source: &[u8]) -> Option<String> {
+    let block = function.child_by_field_name("block")?;
+    let desc = block.named_child(0).filter(|n| n.kind() == NODE_KIND_FUNC_DESC)?;
+    let text = desc.utf8_text(source).ok()?;
+
+    let text = text.trim_start_matches('[').trim_end_matches(']').trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// Parameter of a `param` node, `None` for incomplete parameters while typing
+fn collect_param(param: &Node, source: &[u8]) -> Option<Param> {
+    let name = param.child_by_field_name("name")?.utf8_text(source).ok()?;
+
+    let data_type = param
+        .child_by_field_name("type")
+        .and_then(|t| t.utf8_text(source).ok())
+        .map(String::from);
+    let is_ref = (0..param.child_count())
+        .filter_map(|i| param.child(i))
+        .any(|c| c.kind() == "&");
+
+    Some(Param {
+        name: name.to_string(),
+        data_type,
+        is_ref,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn should_collect_param_types_and_docs() {
+        let source = "// unrelated\n\n/**\n * Opens the door.\n * Returns true on success.\n */\nprotected func Open(int speed, object &by) {}\n\n// first line\n// second line\nfunc Close() {}\n\nfunc Activate() { [Activates the thing] return 1; }\n";
+        let tree = crate::lsp::doc::DocType::Script.get_parser().unwrap().parse(source, None).unwrap();
+        let sigs = SignatureCollector::collect(FileId::from_path("Door.c4d/Script.c").unwrap(), &tree, source).unwrap();
+
+        let open = sigs.get_signature("Open").unwrap();
+        assert_eq!(open.to_signature(), "protected func Open(int speed, object &by)");
+        assert_eq!(open.doc.as_deref(), Some("Opens the door.\nReturns true on success."));
+
+        assert_eq!(sigs.get_signature("Close").unwrap().doc.as_deref(), Some("first line\nsecond line"));
+        assert_eq!(sigs.get_signature("Activate").unwrap().doc.as_deref(), Some("Activates the thing"));
+    }
+}
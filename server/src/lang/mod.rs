@@ -2,18 +2,13 @@ use std::{collections::HashMap, str::FromStr, sync::RwLock};
 
 use lazy_static::lazy_static;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Default)]
 enum SupportedLang {
     De,
+    #[default]
     En,
 }
 
-impl Default for SupportedLang {
-    fn default() -> Self {
-        SupportedLang::En
-    }
-}
-
 impl FromStr for SupportedLang {
     type Err = &'static str;
 
@@ -50,10 +45,10 @@ impl Translation {
     fn get_translation_map(lang: SupportedLang) -> &'static TransMap {
         match lang {
             SupportedLang::De => {
-                &*TRANS_DE
+                &TRANS_DE
             }
             SupportedLang::En => {
-                &*TRANS_EN
+                &TRANS_EN
             }
         }
     }
@@ -61,7 +56,7 @@ impl Translation {
     fn get_current_locale() -> SupportedLang {
         match LOCALE.read() {
             Ok(ref v) => {
-                (*v).clone()
+                **v
             }
             Err(e) => {
                 tracing::error!("Could not get locale: {}", e);
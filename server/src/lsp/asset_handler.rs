@@ -1,9 +1,82 @@
-use tower_lsp::lsp_types::SemanticToken;
+use dashmap::DashMap;
+use tower_lsp::lsp_types::{CompletionResponse, Diagnostic, DocumentSymbolResponse, GotoDefinitionResponse, Location, Position, Range, SemanticToken, SignatureHelp, Url, WorkspaceEdit};
+use crate::core::{embedding::Embedding, project::Project};
 use super::{doc::Document, token_types::TokenTypes};
 
+/// Workspace wide state, which handlers may use to answer requests.
+pub struct HandlerContext<'a> {
+    pub project: &'a Project,
+    pub embedding: &'a Embedding,
+    /// Open documents, which may differ from their state on disk
+    pub documents: &'a DashMap<Url, Document>,
+}
+
 pub trait AssetHandler {
     fn collect_semantic_tokens(&self, tree: &tree_sitter::Tree, lut: TokenTypes, source: &str) -> Vec<SemanticToken>;
-    fn get_hover_text(&self, doc: &Document, pos: tower_lsp::lsp_types::Position) -> Option<String> {
+    fn get_diagnostics(&self, _doc: &Document, _ctx: &HandlerContext) -> Vec<Diagnostic> {
+        vec![]
+    }
+    fn get_hover_text(&self, _doc: &Document, _pos: Position, _ctx: &HandlerContext) -> Option<String> {
+        None
+    }
+    fn get_completion(&self, _doc: &Document, _pos: Position, _ctx: &HandlerContext) -> Option<CompletionResponse> {
+        None
+    }
+    fn get_signature_help(&self, _doc: &Document, _pos: Position, _ctx: &HandlerContext) -> Option<SignatureHelp> {
         None
     }
+    fn get_document_symbols(&self, _doc: &Document) -> Option<DocumentSymbolResponse> {
+        None
+    }
+    fn get_definition(&self, _doc: &Document, _pos: Position, _ctx: &HandlerContext) -> Option<GotoDefinitionResponse> {
+        None
+    }
+    fn get_references(&self, _doc: &Document, _pos: Position, _include_declaration: bool, _ctx: &HandlerContext) -> Option<Vec<Location>> {
+        None
+    }
+    fn prepare_rename(&self, _doc: &Document, _pos: Position) -> Result<Option<Range>, String> {
+        Ok(None)
+    }
+    fn rename(&self, _doc: &Document, _pos: Position, _new_name: &str, _ctx: &HandlerContext) -> Result<Option<WorkspaceEdit>, String> {
+        Ok(None)
+    }
+}
+
+/// Owns the state borrowed by a [`HandlerContext`] in tests.
+#[cfg(test)]
+pub struct TestContext {
+    pub project: Project,
+    pub embedding: Embedding,
+    pub documents: DashMap<Url, Document>,
+}
+
+#[cfg(test)]
+impl Default for TestContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+impl TestContext {
+    /// Context without any indexed definitions
+    pub fn new() -> Self {
+        Self::with_project(Project::new(vec![]))
+    }
+
+    pub fn with_project(project: Project) -> Self {
+        TestContext {
+            project,
+            embedding: Embedding::new(),
+            documents: DashMap::new(),
+        }
+    }
+
+    pub fn ctx(&self) -> HandlerContext<'_> {
+        HandlerContext {
+            project: &self.project,
+            embedding: &self.embedding,
+            documents: &self.documents,
+        }
+    }
 }
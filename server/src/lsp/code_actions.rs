@@ -0,0 +1,62 @@
+use std::collections::HashMap;
+
+use tower_lsp::lsp_types::{CodeAction, CodeActionKind, CodeActionOrCommand, Diagnostic, Url, WorkspaceEdit};
+
+use crate::core::lint::Fix;
+
+use super::diagnostics::DIAGNOSTIC_SOURCE;
+
+/// Quick fixes for the diagnostics of the server, which carry their fix as `data`.
+pub fn quick_fixes(uri: &Url, diagnostics: &[Diagnostic]) -> Vec<CodeActionOrCommand> {
+    diagnostics
+        .iter()
+        .filter(|d| d.source.as_deref() == Some(DIAGNOSTIC_SOURCE))
+        .filter_map(|d| {
+            let fix: Fix = serde_json::from_value(d.data.clone()?).ok()?;
+
+            Some(CodeActionOrCommand::CodeAction(CodeAction {
+                title: fix.title,
+                kind: Some(CodeActionKind::QUICKFIX),
+                diagnostics: Some(vec![d.clone()]),
+                edit: Some(WorkspaceEdit {
+                    changes: Some(HashMap::from([(uri.clone(), fix.edits)])),
+                    ..Default::default()
+                }),
+                is_preferred: Some(true),
+                ..Default::default()
+            }))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use tower_lsp::lsp_types::{DiagnosticSeverity, Position, Range, TextEdit};
+
+    use super::*;
+    use crate::lsp::diagnostics::create_diagnostic;
+
+    #[test]
+    fn should_create_quick_fixes_from_diagnostic_data() {
+        let range = Range::new(Position::new(1, 8), Position::new(1, 9));
+        let fix = Fix {
+            title: String::from("Compare with '=='"),
+            edits: vec![TextEdit::new(range, String::from("=="))],
+        };
+
+        let mut with_fix = create_diagnostic(range, DiagnosticSeverity::WARNING, String::from("assignment"));
+        with_fix.data = serde_json::to_value(&fix).ok();
+        let without_fix = create_diagnostic(range, DiagnosticSeverity::WARNING, String::from("other"));
+
+        let uri = Url::parse("file:///Obj.c4d/Script.c").unwrap();
+        let actions = quick_fixes(&uri, &[with_fix, without_fix]);
+        assert_eq!(actions.len(), 1);
+
+        let CodeActionOrCommand::CodeAction(ref action) = actions[0] else {
+            panic!("expected code action");
+        };
+        assert_eq!(action.title, fix.title);
+        assert_eq!(action.edit.as_ref().unwrap().changes.as_ref().unwrap()[&uri], fix.edits);
+    }
+}
@@ -0,0 +1,39 @@
+use tower_lsp::lsp_types::{GotoDefinitionResponse, Location, Range, Url};
+
+use crate::core::definitions::DefinitionInfo;
+
+/// Start of the `Script.c` of a definition, or of its `DefCore.txt` if it has no script
+pub fn definition_location(info: &DefinitionInfo) -> Option<GotoDefinitionResponse> {
+    let path = info.script_path().unwrap_or_else(|| info.def_core_path());
+    let uri = Url::from_file_path(path).ok()?;
+    Some(GotoDefinitionResponse::Scalar(Location::new(uri, Range::default())))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn should_prefer_the_script_of_definitions() {
+        let root = std::env::temp_dir().join(format!("c4-definition-location-test-{}", std::process::id()));
+        let folder = root.join("Clonk.c4d");
+        fs::create_dir_all(&folder).unwrap();
+        fs::write(folder.join("DefCore.txt"), "[DefCore]\nid=CLNK\n").unwrap();
+
+        let path = |info: &DefinitionInfo| match definition_location(info) {
+            Some(GotoDefinitionResponse::Scalar(location)) => location.uri.to_file_path().unwrap(),
+            _ => panic!("expected a single location"),
+        };
+
+        let info = DefinitionInfo::read(&folder).unwrap();
+        assert_eq!(path(&info), folder.join("DefCore.txt"));
+
+        fs::write(folder.join("Script.c"), "func Initialize() {}\n").unwrap();
+        assert_eq!(path(&info), folder.join("Script.c"));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
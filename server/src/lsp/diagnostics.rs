@@ -0,0 +1,164 @@
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, Range};
+use tree_sitter::{Node, Tree};
+
+use crate::core::parse::node_range;
+
+pub const DIAGNOSTIC_SOURCE: &str = "legacy-clonk-ls";
+
+const MAX_SNIPPET_LENGTH: usize = 20;
+
+pub fn create_diagnostic(range: Range, severity: DiagnosticSeverity, message: String) -> Diagnostic {
+    Diagnostic {
+        range,
+        severity: Some(severity),
+        source: Some(String::from(DIAGNOSTIC_SOURCE)),
+        message,
+        ..Default::default()
+    }
+}
+
+/// Collects diagnostics for all `ERROR` and `MISSING` nodes of the tree.
+/// Only the innermost error nodes are reported, so nested errors do not
+/// produce several diagnostics for the same location.
+pub fn collect_syntax_errors(tree: &Tree, source: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+    let root = tree.root_node();
+
+    if root.has_error() {
+        collect_syntax_errors_step(&root, source.as_bytes(), &mut diagnostics);
+    }
+
+    diagnostics
+}
+
+fn collect_syntax_errors_step(node: &Node, source: &[u8], diagnostics: &mut Vec<Diagnostic>) {
+    let mut cursor = node.walk();
+    let children: Vec<Node> = node.children(&mut cursor).collect();
+
+    if node.is_error() && !children.iter().any(|c| c.has_error()) {
+        let offending = narrow_error(node);
+        diagnostics.push(create_diagnostic(
+            node_range(&offending, source),
+            DiagnosticSeverity::ERROR,
+            error_message(node, &offending, source),
+        ));
+        return;
+    }
+
+    for (idx, child) in children.iter().enumerate() {
+        if child.is_missing() {
+            let prev = idx.checked_sub(1).map(|i| &children[i]);
+            diagnostics.push(create_diagnostic(
+                node_range(child, source),
+                DiagnosticSeverity::ERROR,
+                missing_message(child, prev),
+            ));
+        } else if child.has_error() {
+            collect_syntax_errors_step(child, source, diagnostics);
+        }
+    }
+}
+
+/// Error nodes spanning several lines are narrowed to their first token,
+/// which is the point the parser could not continue from.
+fn narrow_error<'a>(node: &Node<'a>) -> Node<'a> {
+    if node.start_position().row == node.end_position().row {
+        return *node;
+    }
+
+    let mut current = *node;
+    while let Some(child) = current.child(0) {
+        current = child;
+    }
+
+    current
+}
+
+fn missing_message(node: &Node, prev: Option<&Node>) -> String {
+    let expected = describe_kind(node.kind());
+
+    match prev {
+        Some(prev) => format!("expected {} after {}", expected, describe_kind(prev.kind())),
+        None => format!("expected {}", expected),
+    }
+}
+
+fn error_message(error: &Node, offending: &Node, source: &[u8]) -> String {
+    // a complete statement without its terminating ';'
+    let mut cursor = error.walk();
+    let children: Vec<Node> = error.children(&mut cursor).collect();
+    if children.len() == 1 && describe_kind(children[0].kind()) == "expression" {
+        return String::from("expected ';' after expression");
+    }
+
+    let text = offending.utf8_text(source).unwrap_or_default().trim();
+    let snippet: String = text.chars().take(MAX_SNIPPET_LENGTH).collect();
+    let ellipsis = if text.chars().count() > MAX_SNIPPET_LENGTH { "..." } else { "" };
+
+    let context = error
+        .parent()
+        .filter(|p| p.parent().is_some())
+        .map(|p| format!(" in {}", describe_kind(p.kind())))
+        .unwrap_or_default();
+
+    if snippet.is_empty() {
+        format!("syntax error{}", context)
+    } else {
+        format!("unexpected '{}{}'{}", snippet, ellipsis, context)
+    }
+}
+
+/// Human readable description of a node kind or token.
+fn describe_kind(kind: &str) -> String {
+    let is_token = !kind.chars().all(|c| c.is_ascii_lowercase() || c == '_');
+    if is_token {
+        return format!("'{}'", kind);
+    }
+
+    if kind.ends_with("_expression")
+        || matches!(
+            kind,
+            "identifier" | "number" | "string" | "bool" | "nil" | "id" | "method_call" | "array" | "map" | "map_access"
+        )
+    {
+        return String::from("expression");
+    }
+
+    match kind {
+        "var_assignment" | "var_definition" | "var_definition_inline" => String::from("variable declaration"),
+        "args_list" => String::from("arguments"),
+        _ => kind.replace('_', " "),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::lsp::doc::DocType;
+
+    fn diagnostics_for(doc_type: DocType, source: &str) -> Vec<Diagnostic> {
+        let tree = doc_type.get_parser().unwrap().parse(source, None).unwrap();
+        collect_syntax_errors(&tree, source)
+    }
+
+    #[test]
+    fn should_not_report_valid_script() {
+        assert!(diagnostics_for(DocType::Script, "func Foo() { return 1; }").is_empty());
+    }
+
+    #[test]
+    fn should_report_missing_semicolon() {
+        let diagnostics = diagnostics_for(DocType::Script, "func Foo() {\n  var x = 1\n}");
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "expected ';' after variable declaration");
+        assert_eq!(diagnostics[0].range.start.line, 1);
+    }
+
+    #[test]
+    fn should_report_errors_in_ini_files() {
+        let diagnostics = diagnostics_for(DocType::ScenarioTxt, "[Head\nTitle=Foo\n");
+        assert!(!diagnostics.is_empty());
+    }
+}
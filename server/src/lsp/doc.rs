@@ -1,9 +1,8 @@
 use anyhow::{anyhow, Context};
-use tower_lsp::lsp_types::{Position, Url};
-use tracing::info;
-use tree_sitter::{Language, Point, Tree};
-use crate::core::kind::NODE_KIND_FN_DEF;
-use super::{asset_handler::AssetHandler, scenario_txt_handler::ScenarioTxtHandler, script_handler::ScriptHandler};
+use tower_lsp::lsp_types::{Position, TextDocumentContentChangeEvent, Url};
+use tree_sitter::{InputEdit, Language, Point, Tree};
+use super::{asset_handler::AssetHandler, ini::IniHandler, script_handler::ScriptHandler};
+use crate::core::ini::schemas;
 
 pub enum QueryableItem {
     Function(String),
@@ -11,7 +10,7 @@ pub enum QueryableItem {
     Unused,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Document {
     #[allow(dead_code)]
     pub url: Url,
@@ -25,7 +24,7 @@ impl Document {
         Document { url, tree, source, doc_type, }
     }
 
-    pub fn get_node_at_pos(&self, pos: tower_lsp::lsp_types::Position) -> Option<tree_sitter::Node> {
+    pub fn get_node_at_pos(&self, pos: tower_lsp::lsp_types::Position) -> Option<tree_sitter::Node<'_>> {
         let mut cursor = self.tree.walk();
         let point = Document::point_to_pos(pos);
 
@@ -38,52 +37,22 @@ impl Document {
         child.map(|_| cursor.node())
     }
 
-    pub fn get_item_at_pos(&self, pos: Position) -> Option<QueryableItem> {
+    /// Smallest named node at the position. If the position is right behind
+    /// a node (e.g. the cursor is at the end of an identifier), that node is used.
+    pub fn get_named_node_at(&self, pos: Position) -> Option<tree_sitter::Node<'_>> {
+        let (byte, _) = self.position_to_byte(pos);
+        let root = self.tree.root_node();
+        let node = root.named_descendant_for_byte_range(byte, byte)?;
 
-        let mut cursor = self.tree.walk();
-        let point = Document::point_to_pos(pos);
-
-        let mut child = cursor.goto_first_child_for_point(point);
-
-        while let Some(c) = cursor.goto_first_child_for_point(point) {
-            child = Some(c);
-        }
-
-        if let Some(_) = child {
-            let node = cursor.node();
-
-            let text = match node.utf8_text(self.source.as_bytes()) {
-                Ok(s) => {
-                    s
-                },
-                _ => return None,
-            };
-
-            let current_node_kind = node.kind();
-            cursor.goto_parent();
-            let parent_node_kind = cursor.node().kind();
-
-            info!("parent node kind: {}, node kind: {}", parent_node_kind, current_node_kind);
-
-            if parent_node_kind == NODE_KIND_FN_DEF {
-                info!("found function definition; function name: {}", text);
-                Some(QueryableItem::Function(String::from(text)))
-            } else {
-                // we assume, that any other context, in which 
-                // an identifier occurs, must be an expression
-                // and we can check for constants.
-                // if this assumption is wrong, we need a way to check,
-                // if we are inside an expression
-
-                if current_node_kind == "identifier" {
-                    Some(QueryableItem::Constant(String::from(text)))
-                } else {
-                    None
+        if node.child_count() > 0 && byte > 0 {
+            if let Some(prev) = root.named_descendant_for_byte_range(byte - 1, byte - 1) {
+                if prev.child_count() == 0 {
+                    return Some(prev);
                 }
             }
-        } else {
-            None
         }
+
+        Some(node)
     }
 
     pub fn point_to_pos(pos: Position) -> Point {
@@ -92,12 +61,113 @@ impl Document {
             column: pos.character as usize,
         }
     }
+
+    /// Converts a lsp position (utf-16 based columns) into a byte offset
+    /// and the matching tree-sitter point (byte based columns).
+    /// Positions beyond the end of a line or the document are clamped.
+    pub fn position_to_byte(&self, pos: Position) -> (usize, Point) {
+        let bytes = self.source.as_bytes();
+        let mut line_start = 0;
+
+        for _ in 0..pos.line {
+            match bytes[line_start..].iter().position(|b| *b == b'\n') {
+                Some(idx) => line_start += idx + 1,
+                None => {
+                    // line does not exist, clamp to the end of the document
+                    let row = self.source.matches('\n').count();
+                    let column = self.source.len() - self.source.rfind('\n').map_or(0, |i| i + 1);
+                    return (self.source.len(), Point { row, column });
+                }
+            }
+        }
+
+        let line = &self.source[line_start..];
+        let line = &line[..line.find('\n').unwrap_or(line.len())];
+        // the `\r` of a CRLF line ending is no part of the line either
+        let line = line.strip_suffix('\r').unwrap_or(line);
+
+        let mut utf16_col = 0;
+        let mut byte_col = line.len();
+        for (idx, c) in line.char_indices() {
+            if utf16_col >= pos.character as usize {
+                byte_col = idx;
+                break;
+            }
+            utf16_col += c.len_utf16();
+        }
+
+        (
+            line_start + byte_col,
+            Point {
+                row: pos.line as usize,
+                column: byte_col,
+            },
+        )
+    }
+
+    /// Applies the content changes of a `didChange` notification in order
+    /// and re-parses the document once afterwards.
+    /// Ranged changes are translated into `InputEdit`s, so that tree-sitter
+    /// only needs to re-parse the changed regions of the tree.
+    pub fn apply_changes(&mut self, changes: &[TextDocumentContentChangeEvent]) -> anyhow::Result<()> {
+        let mut reuse_tree = true;
+
+        for change in changes {
+            match change.range {
+                Some(range) => {
+                    let (start_byte, start_position) = self.position_to_byte(range.start);
+                    let (old_end_byte, old_end_position) = self.position_to_byte(range.end);
+                    let old_end_byte = old_end_byte.max(start_byte);
+
+                    self.source.replace_range(start_byte..old_end_byte, &change.text);
+
+                    let new_end_position = match change.text.rfind('\n') {
+                        Some(idx) => Point {
+                            row: start_position.row + change.text.matches('\n').count(),
+                            column: change.text.len() - idx - 1,
+                        },
+                        None => Point {
+                            row: start_position.row,
+                            column: start_position.column + change.text.len(),
+                        },
+                    };
+
+                    self.tree.edit(&InputEdit {
+                        start_byte,
+                        old_end_byte,
+                        new_end_byte: start_byte + change.text.len(),
+                        start_position,
+                        old_end_position,
+                        new_end_position,
+                    });
+                }
+                None => {
+                    // full document sync, the old tree is of no use anymore
+                    self.source = change.text.clone();
+                    reuse_tree = false;
+                }
+            }
+        }
+
+        let mut parser = self.doc_type.get_parser()?;
+        let old_tree = if reuse_tree { Some(&self.tree) } else { None };
+
+        self.tree = parser
+            .parse(&self.source, old_tree)
+            .context("Could not update parse tree")?;
+
+        Ok(())
+    }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DocType {
     Script,
     ScenarioTxt,
+    DefCore,
+    ActMap,
+    Material,
+    Particle,
 }
 
 impl DocType {
@@ -105,23 +175,36 @@ impl DocType {
         let file_name = uri
             .path_segments()
             .context("Url cannot be a base")?
-            .last()
+            .next_back()
             .context("Could not extract file name")?;
 
         let ext = file_name
             .split('.')
-            .last()
+            .next_back()
             .context("Could not get file extension")?;
 
         match ext {
             "c" => {
                 Ok(DocType::Script)
             }
+            "c4m" => {
+                Ok(DocType::Material)
+            }
             "txt" => {
                 match file_name {
                     "Scenario.txt" => {
                         Ok(DocType::ScenarioTxt)
                     },
+                    "DefCore.txt" => {
+                        Ok(DocType::DefCore)
+                    },
+                    "ActMap.txt" => {
+                        Ok(DocType::ActMap)
+                    },
+                    // the engine reads `Particle.txt`, `Particles.txt` is common as well
+                    "Particle.txt" | "Particles.txt" => {
+                        Ok(DocType::Particle)
+                    },
                     _ => {
                         Err(anyhow!("File extension '.{}' was recognized, but file name is unknown: {}", &ext, file_name))
                     },
@@ -135,15 +218,21 @@ impl DocType {
 
     pub fn get_handler(&self) -> Box<dyn AssetHandler> {
         match self {
-            DocType::Script => Box::new(ScriptHandler::default()),
-            DocType::ScenarioTxt => Box::new(ScenarioTxtHandler::default()),
+            DocType::Script => Box::new(ScriptHandler),
+            DocType::ScenarioTxt => Box::new(IniHandler { schema: &schemas::SCENARIO_TXT }),
+            DocType::DefCore => Box::new(IniHandler { schema: &schemas::DEF_CORE }),
+            DocType::ActMap => Box::new(IniHandler { schema: &schemas::ACT_MAP }),
+            DocType::Material => Box::new(IniHandler { schema: &schemas::MATERIAL }),
+            DocType::Particle => Box::new(IniHandler { schema: &schemas::PARTICLE }),
         }
     }
 
     pub fn get_language(&self) -> Language {
         match self {
             DocType::Script => tree_sitter_c4script::language(),
-            DocType::ScenarioTxt => tree_sitter_c4ini::language(),
+            DocType::ScenarioTxt | DocType::DefCore | DocType::ActMap | DocType::Material | DocType::Particle => {
+                tree_sitter_c4ini::language()
+            }
         }
     }
 
@@ -156,3 +245,79 @@ impl DocType {
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+
+    use tower_lsp::lsp_types::Range;
+
+    use super::*;
+
+    fn create_doc(source: &str) -> Document {
+        let doc_type = DocType::Script;
+        let tree = doc_type.get_parser().unwrap().parse(source, None).unwrap();
+        Document::new(Url::parse("file:///Script.c").unwrap(), tree, source.to_string(), doc_type)
+    }
+
+    fn ranged_change(start: (u32, u32), end: (u32, u32), text: &str) -> TextDocumentContentChangeEvent {
+        TextDocumentContentChangeEvent {
+            range: Some(Range::new(Position::new(start.0, start.1), Position::new(end.0, end.1))),
+            range_length: None,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn should_convert_utf16_columns_to_bytes() {
+        let doc = create_doc("// äöü\n// 𝄞x");
+
+        assert_eq!(doc.position_to_byte(Position::new(0, 4)), (5, Point { row: 0, column: 5 }));
+        assert_eq!(doc.position_to_byte(Position::new(0, 5)), (7, Point { row: 0, column: 7 }));
+        assert_eq!(doc.position_to_byte(Position::new(1, 5)), (17, Point { row: 1, column: 7 }));
+        assert_eq!(doc.position_to_byte(Position::new(1, 99)), (18, Point { row: 1, column: 8 }));
+        assert_eq!(doc.position_to_byte(Position::new(5, 0)), (18, Point { row: 1, column: 8 }));
+
+        let doc = create_doc("func\r\nFoo");
+        assert_eq!(doc.position_to_byte(Position::new(0, 99)), (4, Point { row: 0, column: 4 }));
+        assert_eq!(doc.position_to_byte(Position::new(1, 1)), (7, Point { row: 1, column: 1 }));
+    }
+
+    #[test]
+    fn should_apply_ranged_changes_in_order() {
+        let mut doc = create_doc("func Foo() {\n  Bar();\n}\n");
+
+        doc.apply_changes(&[
+            ranged_change((1, 2), (1, 5), "Baz"),
+            ranged_change((1, 8), (1, 8), "\n  Qux(1);"),
+        ])
+        .unwrap();
+
+        assert_eq!(doc.source, "func Foo() {\n  Baz();\n  Qux(1);\n}\n");
+
+        let fresh = create_doc(&doc.source);
+        assert_eq!(doc.tree.root_node().to_sexp(), fresh.tree.root_node().to_sexp());
+    }
+
+    #[test]
+    fn should_replace_document_without_range() {
+        let mut doc = create_doc("func Foo() {}");
+
+        doc.apply_changes(&[TextDocumentContentChangeEvent {
+            range: None,
+            range_length: None,
+            text: String::from("local x;"),
+        }])
+        .unwrap();
+
+        assert_eq!(doc.source, "local x;");
+        assert_eq!(doc.tree.root_node().child(0).unwrap().kind(), "var_definition");
+    }
+
+    #[test]
+    fn should_recognize_particle_definitions() {
+        for name in ["Particle.txt", "Particles.txt"] {
+            let uri = Url::parse(&format!("file:///Smoke.c4d/{}", name)).unwrap();
+            assert!(matches!(DocType::from_uri(&uri), Ok(DocType::Particle)));
+        }
+    }
+}
@@ -189,3 +189,13 @@ pub fn add_semantic_token_at(
     }
 }
 
+
+/// Adds a token at a single line range given in the position encoding of the client
+pub fn add_semantic_token_range(ctx: &mut Context, token_type: u32, range: &tower_lsp::lsp_types::Range) {
+    let point = |position: tower_lsp::lsp_types::Position| tree_sitter::Point {
+        row: position.line as usize,
+        column: position.character as usize,
+    };
+
+    add_semantic_token_at(ctx, token_type, point(range.start), point(range.end));
+}
@@ -0,0 +1,244 @@
+use std::collections::BTreeSet;
+
+use tower_lsp::lsp_types::{CompletionItem, CompletionItemKind, CompletionResponse, Position};
+
+use crate::{
+    core::{
+        embedding::Embedding,
+        ini::{schema::Schema, value_type::ValueType},
+    },
+    lsp::{asset_handler::HandlerContext, doc::Document, markdown},
+};
+
+use super::{
+    describe,
+    value_type::{PROCEDURES, SPECIAL_ACTIONS},
+};
+
+/// Completes section names after `[`, keys of the current section and
+/// values of the key in front of the cursor.
+pub fn get_completion(doc: &Document, pos: Position, schema: &Schema, ctx: &HandlerContext) -> Option<CompletionResponse> {
+    let (byte, point) = doc.position_to_byte(pos);
+    let line_prefix = doc.source[byte - point.column..byte].trim_start();
+
+    let items = if line_prefix.starts_with('[') {
+        section_items(schema, &doc.source)
+    } else {
+        let section = current_section(&doc.source, byte)?;
+
+        match line_prefix.split_once('=') {
+            Some((key, value)) => {
+                let def = schema.get_def(section.name, key.trim())?;
+                if is_entering_count(def.value_type, value) {
+                    return None;
+                }
+                value_items(def.value_type, &doc.source, ctx)
+            }
+            None => key_items(schema, &section),
+        }
+    };
+
+    Some(CompletionResponse::Array(items))
+}
+
+/// Section around a byte offset, found by looking at the text only,
+/// as the tree is usually broken while typing.
+pub struct TextSection<'a> {
+    pub name: &'a str,
+    /// Keys of the section, except for the one on the line of the cursor
+    pub keys: Vec<&'a str>,
+}
+
+pub fn current_section(source: &str, byte: usize) -> Option<TextSection<'_>> {
+    let line_start = source[..byte].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = source[byte..].find('\n').map_or(source.len(), |i| byte + i);
+
+    let mut keys = vec![];
+    let mut name = None;
+
+    for line in source[..line_start].lines().rev() {
+        if let Some(header) = section_header(line) {
+            name = Some(header);
+            break;
+        }
+        keys.extend(line_key(line));
+    }
+
+    for line in source[line_end..].lines() {
+        if section_header(line).is_some() {
+            break;
+        }
+        keys.extend(line_key(line));
+    }
+
+    Some(TextSection { name: name?, keys })
+}
+
+fn section_header(line: &str) -> Option<&str> {
+    let line = line.trim();
+    line.strip_prefix('[')?.split(']').next()
+}
+
+fn line_key(line: &str) -> Option<&str> {
+    line.split_once('=').map(|(key, _)| key.trim())
+}
+
+/// Whether the cursor is behind the `=` of an entry of an `IdList` or `MatList`,
+/// e.g. `Rules=REAC=1;FLAG=`.
+fn is_entering_count(value_type: ValueType, value_prefix: &str) -> bool {
+    let is_list = value_type == ValueType::IdList || value_type == ValueType::MatList;
+    is_list && value_prefix.rsplit(';').next().is_some_and(|entry| entry.contains('='))
+}
+
+fn section_items(schema: &Schema, source: &str) -> Vec<CompletionItem> {
+    let present: Vec<&str> = source.lines().filter_map(section_header).collect();
+
+    schema
+        .section_names(&present)
+        .into_iter()
+        .map(|name| CompletionItem {
+            label: name,
+            kind: Some(CompletionItemKind::MODULE),
+            ..Default::default()
+        })
+        .collect()
+}
+
+fn key_items(schema: &Schema, section: &TextSection) -> Vec<CompletionItem> {
+    schema
+        .keys(section.name)
+        .filter(|(key, _)| !section.keys.contains(key))
+        .map(|(key, def)| CompletionItem {
+            label: key.to_string(),
+            kind: Some(CompletionItemKind::PROPERTY),
+            detail: Some(def.value_type.moniker().to_string()),
+            documentation: Some(markdown(describe(key, def))),
+            insert_text: Some(format!("{}=", key)),
+            ..Default::default()
+        })
+        .collect()
+}
+
+fn value_items(value_type: ValueType, source: &str, ctx: &HandlerContext) -> Vec<CompletionItem> {
+    match value_type {
+        ValueType::Procedure => PROCEDURES
+            .iter()
+            .map(|p| CompletionItem {
+                label: p.to_string(),
+                kind: Some(CompletionItemKind::ENUM_MEMBER),
+                ..Default::default()
+            })
+            .collect(),
+        ValueType::Action => action_names(source)
+            .into_iter()
+            .chain(SPECIAL_ACTIONS.iter().copied())
+            .map(|a| CompletionItem {
+                label: a.to_string(),
+                kind: Some(CompletionItemKind::VALUE),
+                ..Default::default()
+            })
+            .collect(),
+        ValueType::Boolean => ["0", "1"]
+            .iter()
+            .map(|v| CompletionItem {
+                label: v.to_string(),
+                kind: Some(CompletionItemKind::VALUE),
+                ..Default::default()
+            })
+            .collect(),
+        ValueType::Category => ctx
+            .embedding
+            .constants()
+            .filter(|(name, _)| name.starts_with("C4D_"))
+            .map(|(name, help)| CompletionItem {
+                label: name.clone(),
+                kind: Some(CompletionItemKind::CONSTANT),
+                detail: help.value.clone(),
+                documentation: Some(markdown(help.description.clone())),
+                ..Default::default()
+            })
+            .collect(),
+        ValueType::Id | ValueType::IdList => definition_ids(ctx)
+            .into_iter()
+            .map(|id| CompletionItem {
+                label: id,
+                kind: Some(CompletionItemKind::CLASS),
+                ..Default::default()
+            })
+            .collect(),
+        ValueType::MatList => material_names(ctx)
+            .into_iter()
+            .map(|name| CompletionItem {
+                label: name,
+                kind: Some(CompletionItemKind::VALUE),
+                ..Default::default()
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Values of all `Name` keys, i.e. the actions of an ActMap.txt
+pub fn action_names(source: &str) -> Vec<&str> {
+    source
+        .lines()
+        .filter_map(|l| l.trim().strip_prefix("Name="))
+        .map(|name| name.trim())
+        .collect()
+}
+
+/// Ids of all definitions in the workspace, including those without a script
+pub fn definition_ids(ctx: &HandlerContext) -> BTreeSet<String> {
+    ctx.project.definitions().map(|d| d.id.clone()).collect()
+}
+
+/// Materials of the engine and the workspace
+pub fn material_names(ctx: &HandlerContext) -> BTreeSet<String> {
+    Embedding::materials()
+        .map(String::from)
+        .chain(ctx.project.materials().cloned())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::{core::project::Project, lsp::asset_handler::TestContext};
+
+    #[test]
+    fn should_find_section_by_text() {
+        let source = "[Head]\nTitle=Foo\n\n[Game]\nMode=1\nGo\nRules=REAC=1;\n[Landscape]\n";
+        let byte = source.find("Go").unwrap() + 2;
+
+        let section = current_section(source, byte).unwrap();
+        assert_eq!(section.name, "Game");
+        assert_eq!(section.keys, vec!["Mode", "Rules"]);
+
+        assert!(current_section("Title=Foo", 3).is_none());
+    }
+
+    #[test]
+    fn should_only_complete_list_entries_in_front_of_equals() {
+        assert!(!is_entering_count(ValueType::IdList, "REAC=1;FL"));
+        assert!(is_entering_count(ValueType::IdList, "REAC=1;FLAG="));
+        assert!(is_entering_count(ValueType::MatList, "Earth=2"));
+        assert!(!is_entering_count(ValueType::Id, ""));
+    }
+
+    #[test]
+    fn should_offer_definitions_without_script() {
+        let root = std::env::temp_dir().join(format!("c4-ini-completion-test-{}", std::process::id()));
+        let folder = root.join("Rock.c4d");
+        std::fs::create_dir_all(&folder).unwrap();
+        std::fs::write(folder.join("DefCore.txt"), "[DefCore]\nid=ROCK\n").unwrap();
+
+        let mut project = Project::new(vec![root.clone()]);
+        project.index_roots();
+        let context = TestContext::with_project(project);
+
+        assert!(definition_ids(&context.ctx()).contains("ROCK"));
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,197 @@
+use tower_lsp::lsp_types::{
+    CompletionResponse, Diagnostic, DocumentSymbolResponse, GotoDefinitionResponse, Position, SemanticToken,
+};
+
+use crate::{
+    core::ini::schema::Schema,
+    lsp::{
+        asset_handler::{AssetHandler, HandlerContext},
+        diagnostics,
+        doc::Document,
+        symbols,
+        token_types::TokenTypes,
+    },
+};
+
+use super::{completion, validation};
+
+/// Handler for ini files, e.g. `DefCore.txt` or `Scenario.txt`.
+/// Everything besides the syntax is driven by the schema of the file type.
+#[derive(Debug, Clone, Copy)]
+pub struct IniHandler {
+    pub schema: &'static Schema,
+}
+
+impl AssetHandler for IniHandler {
+    fn collect_semantic_tokens(&self, tree: &tree_sitter::Tree, lut: TokenTypes, source: &str) -> Vec<SemanticToken> {
+        super::collect_semantic_tokens(tree, lut, source, self.schema)
+    }
+
+    fn get_diagnostics(&self, doc: &Document, ctx: &HandlerContext) -> Vec<Diagnostic> {
+        let mut diagnostics = diagnostics::collect_syntax_errors(&doc.tree, &doc.source);
+        diagnostics.append(&mut validation::validate(doc, self.schema, ctx));
+        diagnostics
+    }
+
+    fn get_document_symbols(&self, doc: &Document) -> Option<DocumentSymbolResponse> {
+        Some(DocumentSymbolResponse::Nested(symbols::collect_ini_symbols(&doc.tree, &doc.source)))
+    }
+
+    fn get_hover_text(&self, doc: &Document, pos: Position, ctx: &HandlerContext) -> Option<String> {
+        super::get_hover_text(doc, pos, self.schema, ctx)
+    }
+
+    fn get_definition(&self, doc: &Document, pos: Position, ctx: &HandlerContext) -> Option<GotoDefinitionResponse> {
+        super::get_definition(doc, pos, self.schema, ctx)
+    }
+
+    fn get_completion(&self, doc: &Document, pos: Position, ctx: &HandlerContext) -> Option<CompletionResponse> {
+        completion::get_completion(doc, pos, self.schema, ctx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use tower_lsp::lsp_types::{CompletionItem, Documentation, Url};
+
+    use super::*;
+    use crate::{
+        core::ini::schemas::{DEF_CORE, MATERIAL, PARTICLE, SCENARIO_TXT},
+        lsp::{asset_handler::TestContext, doc::DocType},
+    };
+
+    fn create_doc(source: &str, uri: &str) -> Document {
+        let uri = Url::parse(uri).unwrap();
+        let doc_type = DocType::from_uri(&uri).unwrap();
+        let tree = doc_type.get_parser().unwrap().parse(source, None).unwrap();
+        Document::new(uri, tree, source.to_string(), doc_type)
+    }
+
+    #[test]
+    fn should_validate_def_core() {
+        let doc = create_doc(
+            "[DefCore]\nid=CLNK\nCategory=C4D_Living|C4D_Foo\nWidth=12\nFoo=1\nCollectible=2\n\n[Physical]\nWalk=50000\n",
+            "file:///Clonk.c4d/DefCore.txt",
+        );
+
+        let context = TestContext::new();
+        let ctx = context.ctx();
+
+        let messages: Vec<(u32, u32, String)> = IniHandler { schema: &DEF_CORE }
+            .get_diagnostics(&doc, &ctx)
+            .into_iter()
+            .map(|d| (d.range.start.line, d.range.start.character, d.message))
+            .collect();
+
+        assert_eq!(
+            messages,
+            vec![
+                (2, 20, String::from("unknown category 'C4D_Foo'")),
+                (4, 0, String::from("unknown key 'Foo' in section [DefCore]")),
+                (5, 12, String::from("expected 0 or 1, found '2'")),
+            ]
+        );
+    }
+
+    fn complete(doc: &Document, pos: Position) -> Vec<CompletionItem> {
+        let context = TestContext::new();
+        let ctx = context.ctx();
+
+        match (IniHandler { schema: &SCENARIO_TXT }).get_completion(doc, pos, &ctx) {
+            Some(CompletionResponse::Array(items)) => items,
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn should_complete_scenario() {
+        let doc = create_doc(
+            "[Head]\nTitle=Test\nIc\n\n[Landscape]\nLayers=Earth=5;\n[\n",
+            "file:///Test.c4s/Scenario.txt",
+        );
+
+        let keys = complete(&doc, Position::new(2, 2));
+        assert!(keys.iter().any(|i| i.label == "Icon"));
+        assert!(!keys.iter().any(|i| i.label == "Title"));
+
+        let icon = keys.iter().find(|i| i.label == "Icon").unwrap();
+        assert!(matches!(&icon.documentation, Some(Documentation::MarkupContent(m)) if m.value.starts_with("**Icon** `Integer`\n\n")));
+
+        let materials = complete(&doc, Position::new(5, 15));
+        assert!(materials.iter().any(|i| i.label == "Granite"));
+        assert!(complete(&doc, Position::new(5, 13)).is_empty());
+
+        let sections = complete(&doc, Position::new(6, 1));
+        assert!(sections.iter().any(|i| i.label == "Player1"));
+    }
+
+    #[test]
+    fn should_validate_scenario() {
+        let doc = create_doc(
+            "[Head]\nTitle=Test\nIcon=x\n\n[Game]\nRules=REAC=1;Flag=1\nClearMaterials=Earth=1;Mud=2\nRules=CNMT=1\n\n[Foo]\nBar=1\n\n[Player7]\nWealth=50\n",
+            "file:///Test.c4s/Scenario.txt",
+        );
+
+        let context = TestContext::new();
+        let ctx = context.ctx();
+
+        let messages: Vec<(u32, u32, u32, String)> = IniHandler { schema: &SCENARIO_TXT }
+            .get_diagnostics(&doc, &ctx)
+            .into_iter()
+            .map(|d| (d.range.start.line, d.range.start.character, d.range.end.character, d.message))
+            .collect();
+
+        assert_eq!(
+            messages,
+            vec![
+                (2, 5, 6, String::from("expected integer, found 'x'")),
+                (5, 13, 17, String::from("expected definition id, found 'Flag'")),
+                (6, 23, 26, String::from("unknown material 'Mud'")),
+                (7, 0, 5, String::from("duplicate key 'Rules' in section [Game]")),
+                (9, 1, 4, String::from("unknown section [Foo]")),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_accept_real_scenario() {
+        let doc = create_doc(include_str!("./testdata/Scenario.txt"), "file:///Alchemy.c4s/Scenario.txt");
+        let context = TestContext::new();
+
+        let diagnostics = IniHandler { schema: &SCENARIO_TXT }.get_diagnostics(&doc, &context.ctx());
+
+        let messages: Vec<(u32, String)> = diagnostics.into_iter().map(|d| (d.range.start.line, d.message)).collect();
+        assert_eq!(messages, vec![]);
+    }
+
+    #[test]
+    fn should_describe_material_keys() {
+        let doc = create_doc("[Material]\nName=Earth\nDensity=50\nInstable=1\n", "file:///Material.c4g/Earth.c4m");
+        assert_eq!(doc.doc_type, DocType::Material);
+
+        let context = TestContext::new();
+        let ctx = context.ctx();
+
+        let handler = IniHandler { schema: &MATERIAL };
+        let hover = handler.get_hover_text(&doc, Position::new(2, 3), &ctx).unwrap();
+        assert!(hover.starts_with("**Density** `Integer`\n\nDensity of the material"));
+        assert!(handler.get_hover_text(&doc, Position::new(0, 3), &ctx).is_none());
+    }
+
+    #[test]
+    fn should_validate_particle() {
+        let doc = create_doc(
+            "[Particle]\nName=Smoke\nMaxCount=abc\nFace=0,0,32,32,-16,-16\nAdditive=1\n",
+            "file:///Smoke.c4d/Particle.txt",
+        );
+
+        let context = TestContext::new();
+        let ctx = context.ctx();
+
+        let diagnostics = IniHandler { schema: &PARTICLE }.get_diagnostics(&doc, &ctx);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range.start, Position::new(2, 9));
+    }
+}
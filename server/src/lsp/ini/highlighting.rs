@@ -0,0 +1,47 @@
+use crate::{
+    core::ini::{ast::IniFile, schema::Schema},
+    lsp::{
+        highlight_helper::{add_semantic_token, Context},
+        token_types::TokenTypes,
+    },
+};
+use tower_lsp::lsp_types::SemanticToken;
+
+pub fn collect_semantic_tokens(
+    tree: &tree_sitter::Tree,
+    lut: TokenTypes,
+    source: &str,
+    schema: &Schema,
+) -> Vec<SemanticToken> {
+    tracing::info!("collecting semantic tokens for ini file");
+
+    let mut c = Context {
+        token_types: lut,
+        ..Context::default()
+    };
+
+    let ctx = &mut c;
+    let file = IniFile::parse(tree, source);
+
+    for section in file.sections.iter() {
+        add_semantic_token(ctx, ctx.token_types.keyword, &section.name_node);
+
+        for property in section.properties.iter() {
+            add_semantic_token(ctx, ctx.token_types.method, &property.key_node);
+
+            if let Some(operator) = property.node.child(1) {
+                add_semantic_token(ctx, ctx.token_types.operator, &operator);
+            }
+
+            let Some(value) = &property.value else {
+                continue;
+            };
+
+            if let Some(def) = schema.get_def(section.name, property.key) {
+                def.value_type.extract_semantic_tokens(value, ctx, source.as_bytes());
+            }
+        }
+    }
+
+    c.collection
+}
@@ -0,0 +1,120 @@
+use tower_lsp::lsp_types::{GotoDefinitionResponse, Position};
+
+use crate::{
+    core::ini::{
+        ast::{AtomKind, IniFile, Property, ValueNode},
+        schema::{Definition, Schema},
+        value_type::ValueType,
+    },
+    lang::Translation,
+};
+
+use super::{asset_handler::HandlerContext, definitions::definition_location, doc::Document};
+
+pub mod completion;
+mod handler;
+mod highlighting;
+mod references;
+pub mod validation;
+pub mod value_type;
+
+pub use handler::IniHandler;
+pub use highlighting::collect_semantic_tokens;
+
+/// Markdown description of a key, e.g. for hovers and completion items
+pub fn describe(key: &str, def: &Definition) -> String {
+    let mut text = format!("**{}** `{}`", key, def.value_type.moniker());
+    if let Some(description) = Translation::get_translation(&def.description) {
+        text.push_str("\n\n");
+        text.push_str(description);
+    }
+
+    text
+}
+
+/// Describes the definition id under the cursor, or the hovered key otherwise.
+pub fn get_hover_text(doc: &Document, pos: Position, schema: &Schema, ctx: &HandlerContext) -> Option<String> {
+    let (byte, _) = doc.position_to_byte(pos);
+    let file = IniFile::parse(&doc.tree, &doc.source);
+
+    let (section, property) = file.property_at(byte)?;
+    let def = schema.get_def(section.name, property.key)?;
+
+    if let Some(info) = id_at(property, def, byte).and_then(|id| ctx.project.definition(id)) {
+        return Some(info.to_help_text(ctx.project.roots()));
+    }
+
+    Some(describe(property.key, def))
+}
+
+/// Location of the definition whose id is under the cursor,
+/// or of the script function named by the value
+pub fn get_definition(doc: &Document, pos: Position, schema: &Schema, ctx: &HandlerContext) -> Option<GotoDefinitionResponse> {
+    let (byte, _) = doc.position_to_byte(pos);
+    let file = IniFile::parse(&doc.tree, &doc.source);
+
+    let (section, property) = file.property_at(byte)?;
+    let def = schema.get_def(section.name, property.key)?;
+
+    if def.value_type == ValueType::Function {
+        return references::function_definition(doc, property.value.as_ref()?.text, ctx);
+    }
+
+    let id = id_at(property, def, byte)?;
+    definition_location(ctx.project.definition(id)?)
+}
+
+/// Definition id at the byte offset inside of an `Id` or `IdList` value
+fn id_at<'a>(property: &Property<'a>, def: &Definition, byte: usize) -> Option<&'a str> {
+    let value = property.value.as_ref()?;
+    let atoms = match (def.value_type, value.parse(def.value_type)) {
+        (ValueType::Id, ValueNode::Scalar(atom)) => vec![atom],
+        (ValueType::IdList, ValueNode::Pairs { pairs, .. }) => pairs.into_iter().map(|p| p.key).collect(),
+        _ => return None,
+    };
+
+    atoms
+        .into_iter()
+        .find(|a| a.kind == AtomKind::Id && a.range.start <= byte && byte <= a.range.end)
+        .map(|a| a.text)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use tower_lsp::lsp_types::Url;
+
+    use super::*;
+    use crate::lsp::{asset_handler::TestContext, doc::DocType};
+
+    #[test]
+    fn should_describe_hovered_key() {
+        let schema = Schema::from_toml("[Head.keys]\nTitle = { type = \"String\", description = \"scenario_txt.head.title\" }\n");
+        let source = "[Head]\nTitle=Foo\n";
+        let tree = DocType::ScenarioTxt.get_parser().unwrap().parse(source, None).unwrap();
+        let doc = Document::new(Url::parse("file:///Scenario.txt").unwrap(), tree, source.to_string(), DocType::ScenarioTxt);
+
+        let context = TestContext::new();
+        let ctx = context.ctx();
+
+        let hover = get_hover_text(&doc, Position::new(1, 2), &schema, &ctx).unwrap();
+        assert!(hover.starts_with("**Title** `String`\n\nTitle for the scenario."));
+        assert!(get_hover_text(&doc, Position::new(0, 2), &schema, &ctx).is_none());
+    }
+
+    #[test]
+    fn should_find_ids_in_values() {
+        let schema = Schema::from_toml("[Game.keys]\nRules = { type = \"IdList\", description = \"a\" }\nTitle = { type = \"String\", description = \"b\" }\n");
+        let source = "[Game]\nRules=REAC=1; FLAG=2\nTitle=CLNK\n";
+        let tree = DocType::ScenarioTxt.get_parser().unwrap().parse(source, None).unwrap();
+        let file = IniFile::parse(&tree, source);
+        let id_at_byte = |byte: usize| {
+            let (section, property) = file.property_at(byte).unwrap();
+            id_at(property, schema.get_def(section.name, property.key).unwrap(), byte)
+        };
+
+        assert_eq!(id_at_byte(source.find("FLAG").unwrap() + 2), Some("FLAG"));
+        assert_eq!(id_at_byte(source.find("=1").unwrap() + 1), None);
+        assert_eq!(id_at_byte(source.find("CLNK").unwrap()), None);
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::HashSet;
+
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, GotoDefinitionResponse, Url};
+
+use crate::{
+    core::{
+        ini::{
+            ast::{IniFile, Value},
+            schema::Schema,
+            value_type::ValueType,
+        },
+        parse::FileId,
+        project::Project,
+        signatures::FileSignatures,
+    },
+    lsp::{
+        asset_handler::HandlerContext,
+        diagnostics::create_diagnostic,
+        doc::Document,
+        script_handler::navigation::{collect_document_signatures, definition_response},
+    },
+};
+
+use super::value_type::SPECIAL_ACTIONS;
+
+const SCRIPT_FILE_NAME: &str = "Script.c";
+
+/// Key naming the action of an `[Action]` section
+const ACTION_NAME_KEY: &str = "Name";
+
+fn values_of_type<'a>(file: &'a IniFile, schema: &Schema, value_type: ValueType) -> Vec<&'a Value<'a>> {
+    let mut values = vec![];
+
+    for section in file.sections.iter() {
+        for property in section.properties.iter() {
+            let (Some(value), Some(def)) = (&property.value, schema.get_def(section.name, property.key)) else {
+                continue;
+            };
+
+            if def.value_type == value_type {
+                values.push(value);
+            }
+        }
+    }
+
+    values
+}
+
+/// Reports references to actions, which are not defined in the same file.
+pub fn validate_action_references(file: &IniFile, schema: &Schema, source: &[u8]) -> Vec<Diagnostic> {
+    let references = values_of_type(file, schema, ValueType::Action);
+    if references.is_empty() {
+        return vec![];
+    }
+
+    let names: HashSet<&str> = file
+        .sections
+        .iter()
+        .flat_map(|s| s.properties.iter())
+        .filter(|p| p.key == ACTION_NAME_KEY)
+        .filter_map(|p| p.value.as_ref().map(|v| v.text))
+        .collect();
+
+    references
+        .into_iter()
+        .filter(|value| !names.contains(value.text) && !SPECIAL_ACTIONS.contains(&value.text))
+        .map(|value| {
+            create_diagnostic(
+                value.range(value.start..value.start + value.text.len(), source),
+                DiagnosticSeverity::ERROR,
+                format!("unknown action '{}'", value.text),
+            )
+        })
+        .collect()
+}
+
+/// Resolves a function, e.g. of a `StartCall`, in the `Script.c` next to the file.
+pub fn function_definition(doc: &Document, name: &str, ctx: &HandlerContext) -> Option<GotoDefinitionResponse> {
+    let path = doc.url.to_file_path().ok()?.parent()?.join(SCRIPT_FILE_NAME);
+    let script_uri = Url::from_file_path(&path).ok()?;
+    let script = sibling_script(&script_uri, ctx)?;
+
+    let matches = ctx.project.find_function_for_script(&script, name);
+    definition_response(matches, &script, &script_uri)
+}
+
+/// The script may be opened with unsaved changes or not be indexed at all.
+fn sibling_script(uri: &Url, ctx: &HandlerContext) -> Option<FileSignatures> {
+    if let Some(doc) = ctx.documents.get(uri) {
+        return collect_document_signatures(&doc, ctx.project);
+    }
+
+    let path = uri.to_file_path().ok()?;
+    if let Some(sigs) = ctx.project.get(&path) {
+        return Some(sigs.clone());
+    }
+
+    let mut sigs = Project::collect_file(FileId::from_path_buf(path.clone())).ok()?;
+    sigs.definition_id = ctx.project.definition_id_for(&path);
+    Some(sigs)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use tower_lsp::lsp_types::Position;
+
+    use super::*;
+    use crate::{core::ini::schemas::ACT_MAP, lsp::doc::DocType};
+
+    #[test]
+    fn should_report_unknown_actions() {
+        let source = "[Action]\nName=Walk\nProcedure=WALK\nNextAction=Walk\n\n[Action]\nName=Jump\nNextAction=Fall\nInLiquidAction=Idle\n";
+        let tree = DocType::ActMap.get_parser().unwrap().parse(source, None).unwrap();
+        let file = IniFile::parse(&tree, source);
+
+        let diagnostics = validate_action_references(&file, &ACT_MAP, source.as_bytes());
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "unknown action 'Fall'");
+        assert_eq!(diagnostics[0].range.start, Position::new(7, 11));
+    }
+}
@@ -0,0 +1,67 @@
+use std::collections::HashSet;
+
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+
+use crate::{
+    core::{
+        ini::{ast::IniFile, schema::Schema},
+        parse::node_range,
+    },
+    lsp::{asset_handler::HandlerContext, diagnostics::create_diagnostic, doc::Document},
+};
+
+use super::references::validate_action_references;
+
+/// Reports unknown sections, unknown and duplicate keys, values not
+/// matching the type of their key and references to unknown actions.
+pub fn validate(doc: &Document, schema: &Schema, ctx: &HandlerContext) -> Vec<Diagnostic> {
+    let source = doc.source.as_bytes();
+    let file = IniFile::parse(&doc.tree, &doc.source);
+    let mut diagnostics = vec![];
+
+    for section in file.sections.iter() {
+        if !schema.has_section(section.name) {
+            diagnostics.push(create_diagnostic(
+                node_range(&section.name_node, source),
+                DiagnosticSeverity::WARNING,
+                format!("unknown section [{}]", section.name),
+            ));
+            continue;
+        }
+
+        let mut seen_keys = HashSet::new();
+        for property in section.properties.iter() {
+            if !seen_keys.insert(property.key) {
+                diagnostics.push(create_diagnostic(
+                    node_range(&property.key_node, source),
+                    DiagnosticSeverity::WARNING,
+                    format!("duplicate key '{}' in section [{}]", property.key, section.name),
+                ));
+            }
+
+            let Some(def) = schema.get_def(section.name, property.key) else {
+                diagnostics.push(create_diagnostic(
+                    node_range(&property.key_node, source),
+                    DiagnosticSeverity::WARNING,
+                    format!("unknown key '{}' in section [{}]", property.key, section.name),
+                ));
+                continue;
+            };
+
+            let Some(value) = &property.value else {
+                continue;
+            };
+
+            for error in def.value_type.validate(&value.parse(def.value_type), ctx) {
+                diagnostics.push(create_diagnostic(
+                    value.range(error.range, source),
+                    error.severity,
+                    error.message,
+                ));
+            }
+        }
+    }
+
+    diagnostics.append(&mut validate_action_references(&file, schema, source));
+    diagnostics
+}
@@ -0,0 +1,267 @@
+use std::ops::Range;
+
+use tower_lsp::lsp_types::DiagnosticSeverity;
+
+use crate::{
+    core::{
+        embedding::Embedding,
+        ini::{
+            ast::{Atom, AtomKind, Value, ValueNode},
+            value_type::{is_id, ValueType},
+        },
+    },
+    lsp::{
+        asset_handler::HandlerContext,
+        highlight_helper::{add_semantic_token_range, Context},
+    },
+};
+
+/// Procedures known to the engine
+pub const PROCEDURES: &[&str] = &[
+    "NONE", "WALK", "FLIGHT", "KNEEL", "SCALE", "HANGLE", "DIG", "SWIM", "THROW", "BRIDGE", "BUILD", "PUSH", "CHOP",
+    "LIFT", "FLOAT", "ATTACH", "FIGHT", "CONNECT", "PULL",
+];
+
+/// Actions which do not have to be defined, see `NextAction`
+pub const SPECIAL_ACTIONS: &[&str] = &["Hold", "Idle"];
+
+/// Problem inside of a value. The range is given in bytes of the source.
+#[derive(Debug, PartialEq)]
+pub struct ValueError {
+    pub range: Range<usize>,
+    pub message: String,
+    pub severity: DiagnosticSeverity,
+}
+
+impl ValueError {
+    fn new(range: Range<usize>, message: String) -> Self {
+        ValueError {
+            range,
+            message,
+            severity: DiagnosticSeverity::ERROR,
+        }
+    }
+
+    /// For values which are well-formed, but might be defined outside of the workspace
+    fn warning(range: Range<usize>, message: String) -> Self {
+        ValueError {
+            range,
+            message,
+            severity: DiagnosticSeverity::WARNING,
+        }
+    }
+}
+
+impl ValueType {
+    fn atom_token_type(&self, atom: &Atom, ctx: &Context) -> u32 {
+        match self {
+            ValueType::String | ValueType::Action | ValueType::MatList => ctx.token_types.string,
+            ValueType::Procedure => ctx.token_types.keyword,
+            ValueType::Function => ctx.token_types.method,
+            ValueType::Id | ValueType::IdList => ctx.token_types.id,
+            ValueType::Category if atom.kind != AtomKind::Integer => ctx.token_types.keyword,
+            ValueType::Integer
+            | ValueType::IntegerList
+            | ValueType::Dword
+            | ValueType::Boolean
+            | ValueType::Category => ctx.token_types.number,
+        }
+    }
+
+    pub fn extract_semantic_tokens(&self, value: &Value, ctx: &mut Context, source: &[u8]) {
+        let add = |ctx: &mut Context, token_type: u32, bytes: Range<usize>| {
+            if !bytes.is_empty() {
+                add_semantic_token_range(ctx, token_type, &value.range(bytes, source));
+            }
+        };
+
+        match value.parse(*self) {
+            ValueNode::Scalar(atom) => {
+                let token_type = self.atom_token_type(&atom, ctx);
+                add(ctx, token_type, atom.range);
+            }
+            ValueNode::List { items, separators } => {
+                let mut separators = separators.into_iter();
+                for atom in items {
+                    if atom.kind != AtomKind::Empty {
+                        let token_type = self.atom_token_type(&atom, ctx);
+                        add(ctx, token_type, atom.range.clone());
+                    }
+                    if let Some(sep) = separators.next().filter(|_| *self == ValueType::Category) {
+                        add(ctx, ctx.token_types.operator, sep..sep + 1);
+                    }
+                }
+            }
+            ValueNode::Pairs { pairs, .. } => {
+                for pair in pairs {
+                    let token_type = self.atom_token_type(&pair.key, ctx);
+                    add(ctx, token_type, pair.key.range);
+                    if let Some(op) = pair.operator {
+                        add(ctx, ctx.token_types.operator, op..op + 1);
+                    }
+                    if let Some(count) = pair.count.filter(|c| c.kind != AtomKind::Empty) {
+                        add(ctx, ctx.token_types.number, count.range);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Checks a parsed value against the type.
+    pub fn validate(&self, value: &ValueNode, ctx: &HandlerContext) -> Vec<ValueError> {
+        let mut errors = vec![];
+
+        match (self, value) {
+            (ValueType::String | ValueType::Action, _) => {}
+            (ValueType::Procedure, ValueNode::Scalar(atom)) => {
+                if !PROCEDURES.contains(&atom.text) {
+                    errors.push(ValueError::new(atom.range.clone(), format!("unknown procedure '{}'", atom.text)));
+                }
+            }
+            (ValueType::Function, ValueNode::Scalar(atom)) => {
+                if !matches!(atom.kind, AtomKind::Identifier | AtomKind::Id) {
+                    errors.push(ValueError::new(
+                        atom.range.clone(),
+                        format!("expected function name, found '{}'", atom.text),
+                    ));
+                }
+            }
+            (ValueType::Integer, ValueNode::Scalar(atom)) => check_integer(atom, &mut errors),
+            (ValueType::Dword, ValueNode::Scalar(atom)) => {
+                if atom.kind != AtomKind::Hex {
+                    check_integer(atom, &mut errors);
+                }
+            }
+            (ValueType::Boolean, ValueNode::Scalar(atom)) => {
+                if atom.text != "0" && atom.text != "1" {
+                    errors.push(ValueError::new(atom.range.clone(), format!("expected 0 or 1, found '{}'", atom.text)));
+                }
+            }
+            (ValueType::IntegerList, ValueNode::List { items, .. }) => {
+                for atom in items {
+                    check_integer(atom, &mut errors);
+                }
+            }
+            (ValueType::Id, ValueNode::Scalar(atom)) => check_id(atom, &mut errors),
+            (ValueType::IdList | ValueType::MatList, ValueNode::Pairs { pairs, .. }) => {
+                for pair in pairs {
+                    if *self == ValueType::IdList {
+                        check_id(&pair.key, &mut errors);
+                    } else if pair.key.kind == AtomKind::Empty {
+                        errors.push(ValueError::new(pair.key.range.clone(), String::from("expected material name")));
+                    } else {
+                        check_material(&pair.key, ctx, &mut errors);
+                    }
+
+                    if let Some(count) = &pair.count {
+                        check_integer(count, &mut errors);
+                    }
+                }
+            }
+            (ValueType::Category, ValueNode::List { items, .. }) => {
+                for atom in items.iter().filter(|a| a.kind != AtomKind::Integer) {
+                    let is_category = atom.text.starts_with("C4D_")
+                        && ctx.embedding.constants().any(|(name, _)| name == atom.text);
+                    if !is_category {
+                        errors.push(ValueError::new(atom.range.clone(), format!("unknown category '{}'", atom.text)));
+                    }
+                }
+            }
+            (value_type, _) => {
+                tracing::error!("value of type {} was not parsed as expected", value_type.moniker());
+            }
+        }
+
+        errors
+    }
+}
+
+fn check_integer(atom: &Atom, errors: &mut Vec<ValueError>) {
+    if atom.kind != AtomKind::Integer {
+        errors.push(ValueError::new(
+            atom.range.clone(),
+            format!("expected integer, found '{}'", atom.text),
+        ));
+    }
+}
+
+pub fn is_material(name: &str, ctx: &HandlerContext) -> bool {
+    Embedding::materials().any(|m| m == name) || ctx.project.materials().any(|m| m == name)
+}
+
+/// Materials may be followed by a texture, e.g. `Earth-Rough`.
+fn check_material(atom: &Atom, ctx: &HandlerContext, errors: &mut Vec<ValueError>) {
+    let material = atom.text.split_once('-').map_or(atom.text, |(material, _)| material);
+
+    if !is_material(material, ctx) {
+        errors.push(ValueError::warning(
+            atom.range.clone(),
+            format!("unknown material '{}'", material),
+        ));
+    }
+}
+
+fn check_id(atom: &Atom, errors: &mut Vec<ValueError>) {
+    // numbers like `1234` are valid ids as well
+    if !is_id(atom.text) {
+        errors.push(ValueError::new(
+            atom.range.clone(),
+            format!("expected definition id, found '{}'", atom.text),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::{
+        core::ini::ast::IniFile,
+        lsp::{asset_handler::TestContext, doc::DocType},
+    };
+
+    #[test]
+    fn should_validate_values() {
+        let context = TestContext::new();
+        let ctx = context.ctx();
+        // ranges are relative to the start of the value
+        let validate = |value_type: ValueType, value: &str| {
+            let source = format!("[A]\nB={}\n", value);
+            let tree = DocType::ScenarioTxt.get_parser().unwrap().parse(&source, None).unwrap();
+            let file = IniFile::parse(&tree, &source);
+            let value = file.sections[0].properties[0].value.as_ref().unwrap();
+
+            value_type
+                .validate(&value.parse(value_type), &ctx)
+                .into_iter()
+                .map(|e| ValueError {
+                    range: e.range.start - value.start..e.range.end - value.start,
+                    ..e
+                })
+                .collect::<Vec<ValueError>>()
+        };
+
+        assert!(validate(ValueType::Integer, "-12").is_empty());
+        assert!(validate(ValueType::Dword, "0xff00ff").is_empty());
+        assert!(validate(ValueType::IdList, "CLNK=2;FLAG=1;").is_empty());
+        assert!(validate(ValueType::Category, "C4D_Living|C4D_SelectBuilding|64").is_empty());
+
+        assert_eq!(
+            validate(ValueType::IntegerList, "1,x,3"),
+            vec![ValueError::new(2..3, String::from("expected integer, found 'x'"))]
+        );
+        assert_eq!(
+            validate(ValueType::IdList, "CLNK=2;Flag=z"),
+            vec![
+                ValueError::new(7..11, String::from("expected definition id, found 'Flag'")),
+                ValueError::new(12..13, String::from("expected integer, found 'z'")),
+            ]
+        );
+        assert_eq!(validate(ValueType::Category, "C4D_Foo")[0].range, 0..7);
+        assert!(validate(ValueType::MatList, "Earth=20;Gold-Rough=5").is_empty());
+        assert_eq!(
+            validate(ValueType::MatList, "Earth=20;Mud=5"),
+            vec![ValueError::warning(9..12, String::from("unknown material 'Mud'"))]
+        );
+    }
+}
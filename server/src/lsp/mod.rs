@@ -1,6 +1,21 @@
+use tower_lsp::lsp_types::{Documentation, MarkupContent, MarkupKind};
+
 pub mod token_types;
 pub mod doc;
 pub mod asset_handler;
-pub mod scenario_txt_handler;
 pub mod script_handler;
 pub mod highlight_helper;
+pub mod diagnostics;
+pub mod code_actions;
+pub mod definitions;
+pub mod ini;
+pub mod symbols;
+pub mod workspace_symbols;
+
+/// Documentation rendered as markdown, e.g. of completion items
+pub fn markdown(value: String) -> Documentation {
+    Documentation::MarkupContent(MarkupContent {
+        kind: MarkupKind::Markdown,
+        value,
+    })
+}
@@ -0,0 +1,40 @@
+use crate::{
+    core::signatures::FileSignatures,
+    lsp::{asset_handler::HandlerContext, doc::Document},
+};
+
+use super::{
+    navigation::collect_document_signatures,
+    scope::{Reference, ScopeResolver},
+    types::TypeInference,
+};
+
+/// Everything the checks of a script build on. Resolving identifiers and
+/// inferring types walks the whole tree, so it is done once for all checks.
+pub struct ScriptAnalysis<'a> {
+    pub doc: &'a Document,
+    pub ctx: &'a HandlerContext<'a>,
+    pub script: &'a FileSignatures,
+    pub resolver: &'a ScopeResolver<'a>,
+    /// Every identifier used as expression
+    pub references: &'a [Reference<'a>],
+    pub inference: &'a TypeInference<'a>,
+}
+
+/// Analyzes the document and passes the analysis to `f`,
+/// `None` if the signatures of the document cannot be collected.
+pub fn analyze<T>(doc: &Document, ctx: &HandlerContext, f: impl FnOnce(&ScriptAnalysis) -> T) -> Option<T> {
+    let script = collect_document_signatures(doc, ctx.project)?;
+    let resolver = ScopeResolver::new(&script, ctx.project, ctx.embedding);
+    let references = resolver.resolve(&doc.tree, &doc.source);
+    let inference = TypeInference::from_references(doc, &script, ctx.project, ctx.embedding, &references);
+
+    Some(f(&ScriptAnalysis {
+        doc,
+        ctx,
+        script: &script,
+        resolver: &resolver,
+        references: &references,
+        inference: &inference,
+    }))
+}
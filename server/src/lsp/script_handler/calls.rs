@@ -0,0 +1,330 @@
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+use tree_sitter::Node;
+
+use crate::{
+    core::{
+        kind::{NODE_KIND_ARGS_LIST, NODE_KIND_METHOD_CALL, NODE_KIND_NUMBER},
+        parse::{node_range, FORWARD_ARGS},
+        project::FunctionMatch,
+        signatures::{C4DataType, FileSignatures},
+    },
+    lsp::{asset_handler::HandlerContext, diagnostics::create_diagnostic},
+};
+
+use super::{analysis::ScriptAnalysis, navigation::FunctionRef, types::TypeInference};
+
+/// Parameters of the called function
+struct Callee {
+    params: Vec<(String, C4DataType)>,
+    /// Whether any number of additional arguments is accepted
+    variadic: bool,
+    /// Whether the declared types are known, which is not the case for overloads
+    typed: bool,
+}
+
+impl Callee {
+    fn from_matches(matches: &[FunctionMatch]) -> Self {
+        if let [(_, sig)] = matches {
+            let params = sig
+                .params
+                .iter()
+                .map(|p| {
+                    let data_type = p.data_type.as_deref().and_then(C4DataType::from_moniker);
+                    (p.name.clone(), data_type.unwrap_or(C4DataType::Any))
+                })
+                .collect();
+
+            return Callee {
+                params,
+                variadic: sig.variadic,
+                typed: true,
+            };
+        }
+
+        // overloads through #appendto may differ, the longest one counts
+        let params = matches
+            .iter()
+            .map(|(_, sig)| &sig.params)
+            .max_by_key(|params| params.len())
+            .map(|params| params.iter().map(|p| (p.name.clone(), C4DataType::Any)).collect())
+            .unwrap_or_default();
+
+        Callee {
+            params,
+            variadic: matches.iter().any(|(_, sig)| sig.variadic),
+            typed: false,
+        }
+    }
+}
+
+/// Checks calls for too many arguments, arguments not matching the type
+/// of their parameter and private functions called from other definitions.
+pub fn check_calls(analysis: &ScriptAnalysis) -> Vec<Diagnostic> {
+    let checker = CallChecker {
+        ctx: analysis.ctx,
+        script: analysis.script,
+        source: analysis.doc.source.as_bytes(),
+        inference: analysis.inference,
+    };
+
+    let mut diagnostics = vec![];
+    checker.check_node(&analysis.doc.tree.root_node(), &mut diagnostics);
+    diagnostics
+}
+
+struct CallChecker<'a> {
+    ctx: &'a HandlerContext<'a>,
+    script: &'a FileSignatures,
+    source: &'a [u8],
+    inference: &'a TypeInference<'a>,
+}
+
+impl CallChecker<'_> {
+    fn check_node(&self, node: &Node, diagnostics: &mut Vec<Diagnostic>) {
+        let mut cursor = node.walk();
+        for child in node.named_children(&mut cursor) {
+            if child.kind() == NODE_KIND_METHOD_CALL {
+                self.check_call(&child, diagnostics);
+            }
+
+            self.check_node(&child, diagnostics);
+        }
+    }
+
+    fn check_call(&self, call: &Node, diagnostics: &mut Vec<Diagnostic>) {
+        let (Some(fn_ref), Some(name), Some(args)) = (
+            FunctionRef::from_call(call, self.source),
+            call.child_by_field_name("name"),
+            call.child_by_field_name("args").filter(|a| a.kind() == NODE_KIND_ARGS_LIST),
+        ) else {
+            return;
+        };
+
+        let matches = fn_ref.resolve(self.ctx.project, self.script);
+        let callee = if matches.is_empty() {
+            let Some(help) = self.ctx.embedding.get_function(fn_ref.name()) else {
+                return;
+            };
+
+            Callee {
+                params: help
+                    .params
+                    .iter()
+                    .take_while(|p| !p.is_variadic())
+                    .map(|p| (p.name.clone(), p.data_type))
+                    .collect(),
+                variadic: help.params.iter().any(|p| p.is_variadic()),
+                typed: true,
+            }
+        } else {
+            if let Some(diagnostic) = self.check_visibility(&fn_ref, &matches, &name) {
+                diagnostics.push(diagnostic);
+            }
+
+            Callee::from_matches(&matches)
+        };
+
+        let (count, forwards) = arguments(&args);
+
+        if !callee.variadic && !forwards && count > callee.params.len() {
+            diagnostics.push(create_diagnostic(
+                node_range(&args, self.source),
+                DiagnosticSeverity::WARNING,
+                format!(
+                    "too many arguments for '{}': expected at most {}, found {}",
+                    fn_ref.name(),
+                    callee.params.len(),
+                    count
+                ),
+            ));
+        }
+
+        if callee.typed && self.script.strict.checks_types() {
+            self.check_types(&args, &callee, diagnostics);
+        }
+    }
+
+    /// Private functions may only be called from within their own definition.
+    fn check_visibility(&self, fn_ref: &FunctionRef, matches: &[FunctionMatch], name: &Node) -> Option<Diagnostic> {
+        if matches!(fn_ref, FunctionRef::Unqualified { .. }) {
+            return None;
+        }
+
+        let own_definition = self.script.definition_id.as_deref();
+        let accessible = matches
+            .iter()
+            .any(|(file, sig)| !sig.is_private() || (own_definition.is_some() && file.definition_id.as_deref() == own_definition));
+
+        if accessible {
+            return None;
+        }
+
+        Some(create_diagnostic(
+            node_range(name, self.source),
+            DiagnosticSeverity::WARNING,
+            format!("private function '{}' called from another definition", fn_ref.name()),
+        ))
+    }
+
+    fn check_types(&self, args: &Node, callee: &Callee, diagnostics: &mut Vec<Diagnostic>) {
+        let mut idx = 0;
+        let mut cursor = args.walk();
+
+        for arg in args.children(&mut cursor) {
+            if arg.kind() == "," {
+                idx += 1;
+                continue;
+            }
+
+            let Some((name, expected)) = callee.params.get(idx).filter(|_| arg.is_named()) else {
+                continue;
+            };
+
+            // `0` is the usual "no object" of legacy scripts
+            let is_zero = arg.kind() == NODE_KIND_NUMBER && arg.utf8_text(self.source).ok() == Some("0");
+            if is_zero && self.script.strict.allows_zero_as_nil() {
+                continue;
+            }
+
+            if let Some(found) = self.inference.type_of(&arg) {
+                if !expected.accepts(found) {
+                    diagnostics.push(create_diagnostic(
+                        node_range(&arg, self.source),
+                        DiagnosticSeverity::WARNING,
+                        format!("expected {} for parameter '{}', found {}", expected.moniker(), name, found.moniker()),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// Number of arguments, including empty ones like in `Foo(1,,3)`,
+/// and whether the parameters of the caller are forwarded with `...`.
+fn arguments(args: &Node) -> (usize, bool) {
+    let mut commas = 0;
+    let mut forwards = false;
+    let mut has_content = false;
+
+    let mut cursor = args.walk();
+    for child in args.children(&mut cursor) {
+        match child.kind() {
+            "," => commas += 1,
+            FORWARD_ARGS => forwards = true,
+            _ => has_content |= child.is_named(),
+        }
+    }
+
+    let count = if commas > 0 || has_content || forwards { commas + 1 } else { 0 };
+    (count, forwards)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::fs;
+
+    use tower_lsp::lsp_types::{Position, Url};
+
+    use super::*;
+    use crate::{
+        core::project::Project,
+        lsp::{
+            asset_handler::TestContext,
+            doc::{DocType, Document},
+            script_handler::analysis::analyze,
+        },
+    };
+
+    fn check(source: &str, uri: &str, context: &TestContext) -> Vec<Diagnostic> {
+        let tree = DocType::Script.get_parser().unwrap().parse(source, None).unwrap();
+        let doc = Document::new(Url::parse(uri).unwrap(), tree, source.to_string(), DocType::Script);
+
+        analyze(&doc, &context.ctx(), check_calls).unwrap()
+    }
+
+    #[test]
+    fn should_warn_about_mismatching_arguments() {
+        let diagnostics = check(
+            "#strict\nfunc Take(int count, obj) {}\nfunc Foo() {\n  var name = \"Clonk\";\n  Take(name, name);\n  Take(true, 0);\n  \
+             CreateObject(\"ROCK\", 0, 0, -1);\n}\n",
+            "file:///Obj.c4d/Script.c",
+            &TestContext::new(),
+        );
+
+        let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0], "expected int for parameter 'count', found string");
+        assert_eq!(diagnostics[0].range.start, Position::new(4, 7));
+        assert!(messages[1].starts_with("expected id for parameter"));
+    }
+
+    #[test]
+    fn should_check_types_by_strict_mode() {
+        let source = "func Take(int count, object obj) {}\nfunc Foo() {\n  Take(\"1\", 0);\n}\n";
+        let context = TestContext::new();
+
+        assert!(check(source, "file:///Obj.c4d/Script.c", &context).is_empty());
+
+        let diagnostics = check(&format!("#strict 3\n{}", source), "file:///Obj.c4d/Script.c", &context);
+        let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "expected int for parameter 'count', found string",
+                "expected object for parameter 'obj', found int",
+            ]
+        );
+    }
+
+    #[test]
+    fn should_check_arity() {
+        let diagnostics = check(
+            "func Take(a, b) {}\nfunc Foo(x) {\n  Take(1, 2, 3);\n  Take(1,);\n  Take(...);\n  Take(1, , , );\n  \
+             Format(\"%d %d\", 1, 2, 3);\n  Sin(1, 2, 3);\n  Second(1, 2, 3);\n  Pass(1, 2);\n}\n\
+             func Second(a) { return Par(1); }\nfunc Pass() { return Take(...); }\n",
+            "file:///Obj.c4d/Script.c",
+            &TestContext::new(),
+        );
+
+        let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "too many arguments for 'Take': expected at most 2, found 3",
+                "too many arguments for 'Take': expected at most 2, found 4",
+                "too many arguments for 'Sin': expected at most 2, found 3",
+            ]
+        );
+    }
+
+    #[test]
+    fn should_warn_about_private_calls() {
+        let root = std::env::temp_dir().join(format!("c4-calls-test-{}", std::process::id()));
+        for (folder, id, script) in [
+            ("Door.c4d", "DOOR", "private func Open() {}\npublic func Close() {}"),
+            ("Clonk.c4d", "CLNK", ""),
+        ] {
+            let def = root.join(folder);
+            fs::create_dir_all(&def).unwrap();
+            fs::write(def.join("DefCore.txt"), format!("[DefCore]\nid={}\n", id)).unwrap();
+            fs::write(def.join("Script.c"), script).unwrap();
+        }
+
+        let mut project = Project::new(vec![root.clone()]);
+        project.index_roots();
+        let context = TestContext::with_project(project);
+
+        let source = "func Foo(door) {\n  door->Open();\n  DOOR::Open();\n  door->Close();\n}\n";
+        let clonk = Url::from_file_path(root.join("Clonk.c4d").join("Script.c")).unwrap();
+        let diagnostics = check(source, clonk.as_str(), &context);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].message, "private function 'Open' called from another definition");
+        assert_eq!(diagnostics[0].range.start, Position::new(1, 8));
+
+        let door = Url::from_file_path(root.join("Door.c4d").join("Script.c")).unwrap();
+        assert!(check(&format!("private func Open() {{}}\n{}", source), door.as_str(), &context).is_empty());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
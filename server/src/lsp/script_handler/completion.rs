@@ -0,0 +1,277 @@
+use std::{collections::HashSet, path::PathBuf};
+
+use tower_lsp::lsp_types::{CompletionItem, CompletionItemKind, CompletionResponse, Position};
+use tree_sitter::Node;
+
+use crate::{
+    core::{
+        embedding::{ConstantHelp, FnHelp},
+        kind::NODE_KIND_FN_DEF,
+        parse::KEYWORDS,
+        signatures::{FileSignatures, Signature, VarScope, Variable},
+    },
+    lsp::{asset_handler::HandlerContext, doc::Document, markdown},
+};
+
+use super::{
+    hover::describe,
+    navigation::collect_document_signatures,
+    scope::{function_variables, Binding},
+};
+
+/// Directives offered along with the keywords
+const DIRECTIVES: &[&str] = &["#include", "#appendto", "#strict", "#strict 2", "#strict 3"];
+
+/// String comparison operators, only offered below `#strict 2`
+const STRING_OPERATORS: &[&str] = &["eq", "ne"];
+
+/// What is in front of the word the cursor is on.
+#[derive(Debug, PartialEq)]
+pub enum CompletionContext {
+    /// `ID::` - functions of the definition
+    Qualified(String),
+    /// `obj->` or `obj->~` - functions of an object of unknown type
+    OnObject,
+    /// Anything else
+    Unqualified,
+}
+
+impl CompletionContext {
+    pub fn from_line_prefix(prefix: &str) -> Self {
+        let before_word = prefix.trim_end_matches(|c: char| c == '_' || c.is_ascii_alphanumeric());
+
+        if let Some(before) = before_word.strip_suffix("::") {
+            let id: String = before.chars().rev().take(4).collect::<Vec<char>>().into_iter().rev().collect();
+            let is_id = id.len() == 4 && id.chars().all(|c| c == '_' || c.is_ascii_uppercase() || c.is_ascii_digit());
+            if is_id {
+                return CompletionContext::Qualified(id);
+            }
+        }
+
+        let before_word = before_word.strip_suffix('~').unwrap_or(before_word);
+        if before_word.trim_end().ends_with("->") {
+            return CompletionContext::OnObject;
+        }
+
+        CompletionContext::Unqualified
+    }
+}
+
+pub fn get_completion(doc: &Document, pos: Position, ctx: &HandlerContext) -> Option<CompletionResponse> {
+    let (byte, point) = doc.position_to_byte(pos);
+    let prefix = &doc.source[byte - point.column..byte];
+    let script = collect_document_signatures(doc, ctx.project)?;
+
+    let mut items = Items::new(ctx.project.roots());
+
+    match CompletionContext::from_line_prefix(prefix) {
+        CompletionContext::Qualified(id) => {
+            for file in ctx.project.definition_chain(&id) {
+                for sig in file.signatures.iter().filter(|s| !s.is_private()) {
+                    items.add_signature(sig, file);
+                }
+            }
+        }
+        CompletionContext::OnObject => {
+            for file in ctx.project.files() {
+                for sig in file.signatures.iter().filter(|s| !s.is_private()) {
+                    items.add_signature(sig, file);
+                }
+            }
+            for (name, help) in ctx.embedding.functions() {
+                items.add_engine_function(name, help);
+            }
+        }
+        CompletionContext::Unqualified => {
+            if let Some(function) = enclosing_function(&doc.tree.root_node(), byte) {
+                for name in variables_before(&function, pos, doc.source.as_bytes()) {
+                    items.add(CompletionItem {
+                        label: name,
+                        kind: Some(CompletionItemKind::VARIABLE),
+                        ..Default::default()
+                    });
+                }
+            }
+
+            let chain = ctx.project.script_chain(&script);
+            for file in chain.iter() {
+                for var in file.variables.iter() {
+                    items.add_variable(var);
+                }
+                for sig in file.signatures.iter() {
+                    items.add_signature(sig, file);
+                }
+            }
+
+            for file in ctx.project.files() {
+                for var in file.variables.iter().filter(|v| v.scope == VarScope::Static) {
+                    items.add_variable(var);
+                }
+                for sig in file.signatures.iter().filter(|s| s.is_global()) {
+                    items.add_signature(sig, file);
+                }
+            }
+
+            for (name, help) in ctx.embedding.functions() {
+                items.add_engine_function(name, help);
+            }
+
+            for (name, help) in ctx.embedding.constants() {
+                items.add_constant(name, help);
+            }
+
+            let allows_string_operators = !script.strict.deprecates_string_operators();
+            let keywords = KEYWORDS
+                .iter()
+                .filter(|k| allows_string_operators || !STRING_OPERATORS.contains(k))
+                .chain(DIRECTIVES);
+
+            for keyword in keywords {
+                items.add(CompletionItem {
+                    label: keyword.to_string(),
+                    kind: Some(CompletionItemKind::KEYWORD),
+                    ..Default::default()
+                });
+            }
+        }
+    }
+
+    Some(CompletionResponse::Array(items.items))
+}
+
+/// Collection of completion items, which only keeps the first item per label.
+/// Items are added from the innermost to the outermost scope.
+struct Items<'a> {
+    labels: HashSet<String>,
+    items: Vec<CompletionItem>,
+    /// Workspace roots, which the origin of functions is shown relative to
+    roots: &'a [PathBuf],
+}
+
+impl<'a> Items<'a> {
+    fn new(roots: &'a [PathBuf]) -> Self {
+        Items {
+            labels: HashSet::new(),
+            items: vec![],
+            roots,
+        }
+    }
+
+    fn add(&mut self, item: CompletionItem) {
+        if self.labels.insert(item.label.clone()) {
+            self.items.push(item);
+        }
+    }
+
+    fn add_signature(&mut self, sig: &Signature, file: &FileSignatures) {
+        self.add(CompletionItem {
+            label: sig.name.clone(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some(sig.to_signature()),
+            documentation: Some(markdown(describe((file, sig), self.roots))),
+            ..Default::default()
+        });
+    }
+
+    fn add_engine_function(&mut self, name: &str, help: &FnHelp) {
+        self.add(CompletionItem {
+            label: name.to_string(),
+            kind: Some(CompletionItemKind::FUNCTION),
+            detail: Some(help.to_signature(name)),
+            documentation: Some(markdown(help.to_help_text(name))),
+            ..Default::default()
+        });
+    }
+
+    fn add_constant(&mut self, name: &String, help: &ConstantHelp) {
+        self.add(CompletionItem {
+            label: name.clone(),
+            kind: Some(CompletionItemKind::CONSTANT),
+            detail: Some(help.data_type.moniker().to_string()),
+            documentation: Some(markdown(help.to_help_text(name))),
+            ..Default::default()
+        });
+    }
+
+    fn add_variable(&mut self, var: &Variable) {
+        let constness = if var.is_const { " const" } else { "" };
+        self.add(CompletionItem {
+            label: var.name.clone(),
+            kind: Some(CompletionItemKind::VARIABLE),
+            detail: Some(format!("{}{} {}", var.scope.moniker(), constness, var.name)),
+            ..Default::default()
+        });
+    }
+}
+
+fn enclosing_function<'a>(root: &Node<'a>, byte: usize) -> Option<Node<'a>> {
+    let mut cursor = root.walk();
+    let function = root
+        .named_children(&mut cursor)
+        .find(|c| c.kind() == NODE_KIND_FN_DEF && c.start_byte() <= byte && byte <= c.end_byte());
+    function
+}
+
+/// Parameters and `var`s of the function declared in front of the position
+fn variables_before(function: &Node, pos: Position, source: &[u8]) -> Vec<String> {
+    let mut variables: Vec<(Position, &str)> = function_variables(function, source)
+        .into_iter()
+        .filter_map(|(name, binding)| match binding {
+            Binding::Param { declaration, .. } | Binding::Var { declaration } => Some((declaration.start, name)),
+            _ => None,
+        })
+        .filter(|(declaration, _)| *declaration < pos)
+        .collect();
+
+    variables.sort();
+    variables.into_iter().map(|(_, name)| name.to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use tower_lsp::lsp_types::{Documentation, Url};
+
+    use super::*;
+    use crate::lsp::{asset_handler::TestContext, doc::DocType};
+
+    #[test]
+    fn should_detect_completion_context() {
+        assert_eq!(CompletionContext::from_line_prefix("  CLNK::Do"), CompletionContext::Qualified(String::from("CLNK")));
+        assert_eq!(CompletionContext::from_line_prefix("  CLNK::"), CompletionContext::Qualified(String::from("CLNK")));
+        assert_eq!(CompletionContext::from_line_prefix("  obj->~Act"), CompletionContext::OnObject);
+        assert_eq!(CompletionContext::from_line_prefix("  obj->"), CompletionContext::OnObject);
+        assert_eq!(CompletionContext::from_line_prefix("  Foo"), CompletionContext::Unqualified);
+        assert_eq!(CompletionContext::from_line_prefix("  Clonk::"), CompletionContext::Unqualified);
+    }
+
+    #[test]
+    fn should_collect_variables_declared_before_cursor() {
+        let source = "func Foo(a, int b) {\n  var c = 1;\n  for (var d in []) {}\n  var e;\n}";
+        let tree = DocType::Script.get_parser().unwrap().parse(source, None).unwrap();
+        let cursor = source.find("var e").unwrap();
+        let pos = Position::new(3, 2);
+
+        let function = enclosing_function(&tree.root_node(), cursor).unwrap();
+        let names = variables_before(&function, pos, source.as_bytes());
+
+        assert_eq!(names, vec!["a", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn should_document_functions_with_their_doc_comment() {
+        let source = "// Opens the door.\nfunc Open() {}\n\nfunc Foo() {\n  Op\n}\n";
+        let tree = DocType::Script.get_parser().unwrap().parse(source, None).unwrap();
+        let doc = Document::new(Url::parse("file:///Door.c4d/Script.c").unwrap(), tree, source.to_string(), DocType::Script);
+        let context = TestContext::new();
+
+        let Some(CompletionResponse::Array(items)) = get_completion(&doc, Position::new(4, 4), &context.ctx()) else {
+            panic!("expected completion items");
+        };
+        let open = items.iter().find(|i| i.label == "Open").unwrap();
+        let Some(Documentation::MarkupContent(ref content)) = open.documentation else {
+            panic!("expected markdown");
+        };
+        assert!(content.value.contains("Opens the door."));
+    }
+}
@@ -279,7 +279,7 @@ mod tests {
 
         let tokens = Highlighter::collect_tokens(&tree, TokenTypes::default());
 
-        assert!(tokens.len() > 0);
+        assert!(!tokens.is_empty());
     }
 
     #[test]
@@ -291,7 +291,7 @@ mod tests {
 
         let tokens = Highlighter::collect_tokens(&tree, TokenTypes::default());
 
-        assert!(tokens.len() > 0);
+        assert!(!tokens.is_empty());
     }
 
     #[test]
@@ -312,7 +312,7 @@ mod tests {
 
         let tokens = Highlighter::collect_tokens(&tree, TokenTypes::default());
 
-        assert!(tokens.len() > 0);
+        assert!(!tokens.is_empty());
     }
 
     #[test]
@@ -323,6 +323,6 @@ mod tests {
         let tree = parser.parse("func GetX() { Explode(100); }", None).unwrap();
         let tokens = Highlighter::collect_tokens(&tree, TokenTypes::default());
 
-        assert!(tokens.len() > 0);
+        assert!(!tokens.is_empty());
     }
 }
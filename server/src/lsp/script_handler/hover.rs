@@ -0,0 +1,215 @@
+use std::path::{Path, PathBuf};
+
+use tower_lsp::lsp_types::Position;
+use tree_sitter::Node;
+
+use crate::{
+    core::{
+        kind::{NODE_KIND_ID, NODE_KIND_PRAGMA_STRICT},
+        project::FunctionMatch,
+        signatures::{FileSignatures, StrictMode},
+    },
+    lsp::{
+        asset_handler::HandlerContext,
+        doc::{Document, QueryableItem},
+    },
+};
+
+use super::{
+    navigation::{collect_document_signatures, FunctionRef},
+    scope::Binding,
+    types::TypeInference,
+};
+
+/// Describes the function, definition id or `#strict` pragma at the position. Script functions
+/// are preferred, as they overload engine functions of the same name.
+pub fn get_hover_text(doc: &Document, pos: Position, ctx: &HandlerContext) -> Option<String> {
+    let node = doc.get_named_node_at(pos);
+
+    if let Some(id) = node.filter(|n| n.kind() == NODE_KIND_ID) {
+        let id = id.utf8_text(doc.source.as_bytes()).ok()?;
+        return ctx.project.definition(id).map(|info| info.to_help_text(ctx.project.roots()));
+    }
+
+    if let Some(pragma) = node.filter(|n| n.kind() == NODE_KIND_PRAGMA_STRICT) {
+        let mode = StrictMode::from_pragma(pragma.utf8_text(doc.source.as_bytes()).ok()?);
+        return Some(format!("`{}`\n\n{}", mode.moniker(), mode.describe()));
+    }
+
+    let fn_ref = node.and_then(|node| FunctionRef::from_node(&node, doc.source.as_bytes()));
+
+    match fn_ref {
+        Some(fn_ref) => describe_user_function(doc, &fn_ref, ctx).or_else(|| {
+            ctx.embedding
+                .get_function(fn_ref.name())
+                .map(|help| help.to_help_text(fn_ref.name()))
+        }),
+        None => describe_variable(doc, &node?, ctx),
+    }
+}
+
+/// Describes the parameter, variable or constant an identifier refers to.
+fn describe_variable(doc: &Document, node: &Node, ctx: &HandlerContext) -> Option<String> {
+    let script = collect_document_signatures(doc, ctx.project)?;
+    let inference = TypeInference::new(doc, &script, ctx.project, ctx.embedding);
+    let binding = inference.binding(node)?;
+
+    let name = node.utf8_text(doc.source.as_bytes()).ok()?;
+    let (declaration, declared_in) = match binding {
+        Binding::Constant => {
+            return ctx.embedding.query_signature(QueryableItem::Constant(name.to_string()));
+        }
+        Binding::Param { data_type: Some(t), .. } => (format!("{} {}", t, name), None),
+        Binding::Param { data_type: None, .. } | Binding::Var { .. } => match inference.binding_type(binding) {
+            Some(t) => (format!("var {}: {}", name, t.moniker()), None),
+            None => (format!("var {}", name), None),
+        },
+        Binding::Script { file_id, variable } => {
+            let constness = if variable.is_const { " const" } else { "" };
+            (format!("{}{} {}", variable.scope.moniker(), constness, name), Some(file_id))
+        }
+    };
+
+    let mut text = format!("```c4script\n{}\n```", declaration);
+
+    // variables of other scripts are described with their origin
+    if let Some(file) = declared_in
+        .filter(|f| **f != script.file_id)
+        .and_then(|f| ctx.project.get(&f.path))
+    {
+        text.push_str("\n\n");
+        text.push_str(&origin(file, ctx.project.roots()));
+    }
+
+    Some(text)
+}
+
+fn describe_user_function(doc: &Document, fn_ref: &FunctionRef, ctx: &HandlerContext) -> Option<String> {
+    let script = collect_document_signatures(doc, ctx.project)?;
+
+    let matches = fn_ref.resolve(ctx.project, &script);
+    if matches.is_empty() {
+        return None;
+    }
+
+    let texts: Vec<String> = matches
+        .into_iter()
+        .map(|m| describe(m, ctx.project.roots()))
+        .collect();
+
+    Some(texts.join("\n\n---\n\n"))
+}
+
+/// Signature, doc comment and origin of a function
+pub fn describe((file, sig): FunctionMatch, roots: &[PathBuf]) -> String {
+    let mut text = format!("```c4script\n{}\n```", sig.to_signature());
+
+    if let Some(ref doc) = sig.doc {
+        text.push_str("\n\n");
+        text.push_str(doc);
+    }
+
+    text.push_str("\n\n");
+    text.push_str(&origin(file, roots));
+    text
+}
+
+/// Definition id and path of the file, relative to its workspace root
+fn origin(file: &FileSignatures, roots: &[PathBuf]) -> String {
+    let path: &Path = file.file_id.path.as_ref();
+    let relative = roots
+        .iter()
+        .find_map(|root| path.strip_prefix(root).ok())
+        .unwrap_or(path);
+
+    match file.definition_id {
+        Some(ref id) => format!("`{}` ({})", id, relative.display()),
+        None => format!("({})", relative.display()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use tower_lsp::lsp_types::{GotoDefinitionResponse, Url};
+
+    use super::*;
+    use crate::{
+        core::project::Project,
+        lsp::{asset_handler::TestContext, doc::DocType, script_handler::navigation},
+    };
+
+    #[test]
+    fn should_describe_user_function() {
+        let source = "/* Opens the door */\nprivate func Open(int speed) {}\n\nfunc Activate() { Open(2); CreateObject(FLAG); }\n";
+        let tree = DocType::Script.get_parser().unwrap().parse(source, None).unwrap();
+        let doc = Document::new(Url::parse("file:///Door.c4d/Script.c").unwrap(), tree, source.to_string(), DocType::Script);
+
+        let context = TestContext::new();
+        let ctx = context.ctx();
+
+        let hover = get_hover_text(&doc, Position::new(3, 19), &ctx).unwrap();
+        assert_eq!(hover, "```c4script\nprivate func Open(int speed)\n```\n\nOpens the door\n\n(/Door.c4d/Script.c)");
+
+        // engine functions are still described by the embedding
+        let hover = get_hover_text(&doc, Position::new(3, 30), &ctx).unwrap();
+        assert!(hover.contains("CreateObject"));
+    }
+
+    #[test]
+    fn should_describe_definition_ids() {
+        let root = std::env::temp_dir().join(format!("c4-hover-id-test-{}", std::process::id()));
+        let folder = root.join("Clonk.c4d");
+        std::fs::create_dir_all(&folder).unwrap();
+        std::fs::write(folder.join("DefCore.txt"), "[DefCore]\nid=CLNK\nName=Clonk\nWidth=12\nHeight=20\n").unwrap();
+
+        let mut project = Project::new(vec![root.clone()]);
+        project.index_roots();
+        let context = TestContext::with_project(project);
+        let ctx = context.ctx();
+
+        let source = "func Foo() { CreateObject(CLNK); }";
+        let tree = DocType::Script.get_parser().unwrap().parse(source, None).unwrap();
+        let doc = Document::new(Url::parse("file:///Foo.c4d/Script.c").unwrap(), tree, source.to_string(), DocType::Script);
+
+        let hover = get_hover_text(&doc, Position::new(0, 27), &ctx).unwrap();
+        assert_eq!(hover, "**Clonk** `CLNK`\n\nSize: 12 x 20\n\n(Clonk.c4d)");
+
+        let definition = navigation::get_definition(&doc, Position::new(0, 27), ctx.project);
+        assert!(matches!(definition, Some(GotoDefinitionResponse::Scalar(l)) if l.uri.path().ends_with("Clonk.c4d/DefCore.txt")));
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn should_describe_variables() {
+        let source = "local count;\nfunc Foo(int amount, obj) {\n  var sum = amount + count;\n  return sum + obj + NO_OWNER + C4D_Living;\n}\n";
+        let tree = DocType::Script.get_parser().unwrap().parse(source, None).unwrap();
+        let doc = Document::new(Url::parse("file:///Foo.c4d/Script.c").unwrap(), tree, source.to_string(), DocType::Script);
+
+        let context = TestContext::new();
+        let ctx = context.ctx();
+
+        let hover = |line, character| get_hover_text(&doc, Position::new(line, character), &ctx);
+        assert_eq!(hover(2, 14).unwrap(), "```c4script\nint amount\n```");
+        assert_eq!(hover(2, 23).unwrap(), "```c4script\nlocal count\n```");
+        assert_eq!(hover(3, 10).unwrap(), "```c4script\nvar sum: int\n```");
+        assert_eq!(hover(3, 16).unwrap(), "```c4script\nvar obj\n```");
+        assert!(hover(3, 35).unwrap().contains("C4D_Living"));
+    }
+
+    #[test]
+    fn should_describe_strict_pragma() {
+        let source = "#strict 2
+func Foo() {}
+";
+        let tree = DocType::Script.get_parser().unwrap().parse(source, None).unwrap();
+        let doc = Document::new(Url::parse("file:///Foo.c4d/Script.c").unwrap(), tree, source.to_string(), DocType::Script);
+
+        let context = TestContext::new();
+        let ctx = context.ctx();
+
+        let hover = get_hover_text(&doc, Position::new(0, 3), &ctx).unwrap();
+        assert!(hover.starts_with("`#strict 2`\n\nParameter types are checked"));
+    }
+}
@@ -0,0 +1,45 @@
+use std::collections::HashMap;
+
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticTag, NumberOrString};
+
+use crate::{
+    core::lint::{self, LintContext, Report},
+    lsp::diagnostics::create_diagnostic,
+};
+
+use super::analysis::ScriptAnalysis;
+
+/// Runs the lint rules with the configuration of the workspace.
+pub fn lint_script(analysis: &ScriptAnalysis) -> Vec<Diagnostic> {
+    let (doc, ctx) = (analysis.doc, analysis.ctx);
+
+    // the nearest declaration wins, like in the scope resolution
+    let mut script_variables = HashMap::new();
+    for file in ctx.project.script_chain(analysis.script) {
+        for var in file.variables.iter() {
+            script_variables.entry(var.name.as_str()).or_insert(var.scope);
+        }
+    }
+
+    let lint_ctx = LintContext {
+        tree: &doc.tree,
+        source: doc.source.as_bytes(),
+        script_variables,
+        types: analysis.inference,
+    };
+
+    lint::run(&lint_ctx, ctx.project.lint_config())
+        .into_iter()
+        .map(to_diagnostic)
+        .collect()
+}
+
+/// Diagnostic of a report, which carries the fix of the finding as `data` for the code actions
+fn to_diagnostic(report: Report) -> Diagnostic {
+    let finding = report.finding;
+    let mut diagnostic = create_diagnostic(finding.range, report.severity, finding.message);
+    diagnostic.code = Some(NumberOrString::String(report.code.to_string()));
+    diagnostic.tags = finding.unnecessary.then(|| vec![DiagnosticTag::UNNECESSARY]);
+    diagnostic.data = finding.fix.and_then(|fix| serde_json::to_value(fix).ok());
+    diagnostic
+}
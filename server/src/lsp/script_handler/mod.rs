@@ -1,7 +1,19 @@
-use tower_lsp::lsp_types::SemanticToken;
-use super::{asset_handler::AssetHandler, token_types::TokenTypes};
+use tower_lsp::lsp_types::{CompletionResponse, Diagnostic, DocumentSymbolResponse, GotoDefinitionResponse, Location, Position, Range, SemanticToken, SignatureHelp, WorkspaceEdit};
+use super::{asset_handler::{AssetHandler, HandlerContext}, diagnostics, doc::Document, token_types::TokenTypes};
 
+mod analysis;
+mod calls;
+pub mod completion;
 mod highlighting;
+mod hover;
+mod lint;
+pub mod navigation;
+pub mod references;
+pub mod scope;
+mod signature_help;
+mod strict;
+mod symbols;
+mod types;
 
 #[derive(Debug, Clone, Default)]
 pub struct ScriptHandler;
@@ -10,4 +22,47 @@ impl AssetHandler for ScriptHandler {
     fn collect_semantic_tokens(&self, tree: &tree_sitter::Tree, lut: TokenTypes, _: &str) -> Vec<SemanticToken> {
         highlighting::Highlighter::collect_tokens(tree, lut)
     }
+
+    fn get_diagnostics(&self, doc: &Document, ctx: &HandlerContext) -> Vec<Diagnostic> {
+        let mut diagnostics = diagnostics::collect_syntax_errors(&doc.tree, &doc.source);
+        analysis::analyze(doc, ctx, |analysis| {
+            diagnostics.append(&mut scope::undefined_identifiers(analysis));
+            diagnostics.append(&mut calls::check_calls(analysis));
+            diagnostics.append(&mut strict::check_strict_mode(analysis));
+            diagnostics.append(&mut lint::lint_script(analysis));
+        });
+        diagnostics
+    }
+
+    fn get_hover_text(&self, doc: &Document, pos: Position, ctx: &HandlerContext) -> Option<String> {
+        hover::get_hover_text(doc, pos, ctx)
+    }
+
+    fn get_completion(&self, doc: &Document, pos: Position, ctx: &HandlerContext) -> Option<CompletionResponse> {
+        completion::get_completion(doc, pos, ctx)
+    }
+
+    fn get_signature_help(&self, doc: &Document, pos: Position, ctx: &HandlerContext) -> Option<SignatureHelp> {
+        signature_help::get_signature_help(doc, pos, ctx)
+    }
+
+    fn get_document_symbols(&self, doc: &Document) -> Option<DocumentSymbolResponse> {
+        Some(DocumentSymbolResponse::Nested(symbols::get_document_symbols(doc)))
+    }
+
+    fn get_definition(&self, doc: &Document, pos: Position, ctx: &HandlerContext) -> Option<GotoDefinitionResponse> {
+        navigation::get_definition(doc, pos, ctx.project)
+    }
+
+    fn get_references(&self, doc: &Document, pos: Position, include_declaration: bool, ctx: &HandlerContext) -> Option<Vec<Location>> {
+        references::find_references(doc, pos, include_declaration, ctx)
+    }
+
+    fn prepare_rename(&self, doc: &Document, pos: Position) -> Result<Option<Range>, String> {
+        references::prepare_rename(doc, pos)
+    }
+
+    fn rename(&self, doc: &Document, pos: Position, new_name: &str, ctx: &HandlerContext) -> Result<Option<WorkspaceEdit>, String> {
+        references::rename(doc, pos, new_name, ctx)
+    }
 }
@@ -0,0 +1,155 @@
+use std::path::PathBuf;
+
+use tower_lsp::lsp_types::{GotoDefinitionResponse, Location, Position, Url};
+use tree_sitter::Node;
+
+use crate::{
+    core::{
+        kind::{NODE_KIND_ARROW_EXPRESSION, NODE_KIND_FN_DEF, NODE_KIND_ID, NODE_KIND_IDENTIFIER, NODE_KIND_METHOD_CALL},
+        parse::FileId,
+        project::{FunctionMatch, Project},
+        signatures::{FileSignatures, SignatureCollector},
+    },
+    lsp::{definitions::definition_location, doc::Document},
+};
+
+/// A function referenced at some position in a script.
+pub enum FunctionRef {
+    /// Call qualified by a definition id, e.g. `CLNK::DoStuff()`
+    Qualified { id: String, name: String },
+    /// Call on an object of unknown type, e.g. `obj->~Activate()`
+    OnObject { name: String },
+    /// Unqualified call or the name of a function definition
+    Unqualified { name: String },
+}
+
+impl FunctionRef {
+    pub fn name(&self) -> &str {
+        match self {
+            FunctionRef::Qualified { name, .. } => name,
+            FunctionRef::OnObject { name } => name,
+            FunctionRef::Unqualified { name } => name,
+        }
+    }
+
+    pub fn from_node(node: &Node, source: &[u8]) -> Option<Self> {
+        if node.kind() != NODE_KIND_IDENTIFIER {
+            return None;
+        }
+
+        let parent = node.parent()?;
+        let name = node.utf8_text(source).ok()?.to_string();
+
+        match parent.kind() {
+            NODE_KIND_FN_DEF if parent.child_by_field_name("name") == Some(*node) => {
+                Some(FunctionRef::Unqualified { name })
+            }
+            NODE_KIND_METHOD_CALL if parent.child_by_field_name("name") == Some(*node) => {
+                Self::from_call(&parent, source)
+            }
+            _ => None,
+        }
+    }
+
+    /// Reference to the function called by a `method_call` node.
+    pub fn from_call(call: &Node, source: &[u8]) -> Option<Self> {
+        let name = call.child_by_field_name("name")?.utf8_text(source).ok()?.to_string();
+
+        if let Some(id) = call.child_by_field_name("id") {
+            let id = id.utf8_text(source).ok()?.to_string();
+            Some(FunctionRef::Qualified { id, name })
+        } else if call
+            .parent()
+            .is_some_and(|p| p.kind() == NODE_KIND_ARROW_EXPRESSION)
+        {
+            Some(FunctionRef::OnObject { name })
+        } else {
+            Some(FunctionRef::Unqualified { name })
+        }
+    }
+
+    pub fn resolve<'a>(&self, project: &'a Project, script: &'a FileSignatures) -> Vec<FunctionMatch<'a>> {
+        match self {
+            FunctionRef::Qualified { id, name } => project.find_function(id, name),
+            FunctionRef::OnObject { name } => project.find_function_anywhere(name),
+            FunctionRef::Unqualified { name } => project.find_function_for_script(script, name),
+        }
+    }
+}
+
+/// Collects the signatures of the (possibly unsaved) document itself,
+/// so lookups see the current state of the script.
+/// Documents without a file, like untitled buffers, get an id which is never indexed.
+pub fn collect_document_signatures(doc: &Document, project: &Project) -> Option<FileSignatures> {
+    let path = doc.url.to_file_path().ok();
+    let file_id = FileId::from_path_buf(path.clone().unwrap_or_else(|| PathBuf::from(doc.url.as_str())));
+    let mut sigs = SignatureCollector::collect(file_id, &doc.tree, &doc.source).ok()?;
+
+    sigs.definition_id = path.and_then(|p| project.definition_id_for(&p));
+    Some(sigs)
+}
+
+pub fn get_definition(doc: &Document, pos: Position, project: &Project) -> Option<GotoDefinitionResponse> {
+    let node = doc.get_named_node_at(pos)?;
+
+    if node.kind() == NODE_KIND_ID {
+        let id = node.utf8_text(doc.source.as_bytes()).ok()?;
+        return definition_location(project.definition(id)?);
+    }
+
+    let fn_ref = FunctionRef::from_node(&node, doc.source.as_bytes())?;
+    let script = collect_document_signatures(doc, project)?;
+
+    definition_response(fn_ref.resolve(project, &script), &script, &doc.url)
+}
+
+/// Locations of the matched functions. Matches in `script` point to
+/// `script_uri`, as the script may be an open document.
+pub fn definition_response(
+    matches: Vec<FunctionMatch>,
+    script: &FileSignatures,
+    script_uri: &Url,
+) -> Option<GotoDefinitionResponse> {
+    let locations: Vec<Location> = matches
+        .into_iter()
+        .filter_map(|(file, sig)| {
+            let uri = if file.file_id == script.file_id {
+                script_uri.clone()
+            } else {
+                Url::from_file_path(file.file_id.path.as_ref()).ok()?
+            };
+
+            Some(Location::new(uri, sig.name_range))
+        })
+        .collect();
+
+    match locations.len() {
+        0 => None,
+        1 => Some(GotoDefinitionResponse::Scalar(locations[0].clone())),
+        _ => Some(GotoDefinitionResponse::Array(locations)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::lsp::doc::DocType;
+
+    #[test]
+    fn should_resolve_functions_of_untitled_documents() {
+        let source = "func Foo() {}\nfunc Bar() { Foo(); }\n";
+        let tree = DocType::Script.get_parser().unwrap().parse(source, None).unwrap();
+        let uri = Url::parse("untitled:Untitled-1").unwrap();
+        let doc = Document::new(uri.clone(), tree, source.to_string(), DocType::Script);
+        let project = Project::new(vec![]);
+
+        assert!(collect_document_signatures(&doc, &project).is_some_and(|s| s.definition_id.is_none()));
+
+        let Some(GotoDefinitionResponse::Scalar(location)) = get_definition(&doc, Position::new(1, 14), &project) else {
+            panic!("expected a single location");
+        };
+        assert_eq!(location.uri, uri);
+        assert_eq!(location.range.start, Position::new(0, 5));
+    }
+}
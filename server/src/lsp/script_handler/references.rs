@@ -0,0 +1,536 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::PathBuf,
+};
+
+use tower_lsp::lsp_types::{Location, Position, Range, TextEdit, Url, WorkspaceEdit};
+use tree_sitter::{Node, Tree};
+
+use crate::{
+    core::{
+        embedding::Embedding,
+        kind::{
+            NODE_KIND_FN_DEF, NODE_KIND_FOR_STATEMENT, NODE_KIND_IDENTIFIER, NODE_KIND_METHOD_CALL,
+            NODE_KIND_PARAM, NODE_KIND_VAR_ASSIGNMENT,
+        },
+        parse::{is_declaration, is_variable, node_range, var_scope_of, FileId, KEYWORDS},
+        signatures::FileSignatures,
+    },
+    lsp::{asset_handler::HandlerContext, doc::{DocType, Document}},
+};
+
+use super::navigation::{collect_document_signatures, FunctionRef};
+
+/// A symbol, which can be referenced and renamed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Symbol {
+    /// Function of any script
+    Function(String),
+    /// `local` or `static` variable declared on script level
+    ScriptVariable { name: String, is_static: bool },
+    /// Parameter or `var` of a function. The function is identified by its byte range.
+    FunctionVariable { name: String, function: std::ops::Range<usize> },
+}
+
+impl Symbol {
+    pub fn name(&self) -> &str {
+        match self {
+            Symbol::Function(name) => name,
+            Symbol::ScriptVariable { name, .. } => name,
+            Symbol::FunctionVariable { name, .. } => name,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Occurrence {
+    range: Range,
+    start_byte: usize,
+    is_declaration: bool,
+}
+
+/// Resolves the symbol of the identifier at the given position.
+pub fn symbol_at(doc: &Document, pos: Position) -> Option<(Symbol, Range)> {
+    let node = doc.get_named_node_at(pos)?;
+    if node.kind() != NODE_KIND_IDENTIFIER {
+        return None;
+    }
+
+    let source = doc.source.as_bytes();
+    let name = node.utf8_text(source).ok()?.to_string();
+    let range = node_range(&node, source);
+    let parent = node.parent()?;
+
+    if is_function_name(&node, &parent) {
+        return Some((Symbol::Function(name), range));
+    }
+
+    if !is_variable(&node, &parent) {
+        return None;
+    }
+
+    if let Some(function) = enclosing_function(&node) {
+        if declares_variable(&function, &name, source) {
+            let function = function.byte_range();
+            return Some((Symbol::FunctionVariable { name, function }, range));
+        }
+    }
+
+    script_variable(doc.tree.root_node(), &name, source).map(|symbol| (symbol, range))
+}
+
+pub fn find_references(
+    doc: &Document,
+    pos: Position,
+    include_declaration: bool,
+    ctx: &HandlerContext,
+) -> Option<Vec<Location>> {
+    let (symbol, _) = symbol_at(doc, pos)?;
+
+    let locations = collect_occurrences(doc, pos, &symbol, ctx)
+        .into_iter()
+        .flat_map(|(uri, occurrences)| {
+            occurrences
+                .into_iter()
+                .filter(|o| include_declaration || !o.is_declaration)
+                .map(move |o| Location::new(uri.clone(), o.range))
+        })
+        .collect();
+
+    Some(locations)
+}
+
+/// Range of the symbol to rename, or an explanation why it cannot be renamed.
+pub fn prepare_rename(doc: &Document, pos: Position) -> Result<Option<Range>, String> {
+    let Some((symbol, range)) = symbol_at(doc, pos) else {
+        return Ok(None);
+    };
+
+    check_renamable(&symbol)?;
+    Ok(Some(range))
+}
+
+pub fn rename(
+    doc: &Document,
+    pos: Position,
+    new_name: &str,
+    ctx: &HandlerContext,
+) -> Result<Option<WorkspaceEdit>, String> {
+    let Some((symbol, _)) = symbol_at(doc, pos) else {
+        return Ok(None);
+    };
+
+    check_renamable(&symbol)?;
+
+    if !is_valid_identifier(new_name) {
+        return Err(format!("'{}' is not a valid identifier", new_name));
+    }
+
+    let changes: HashMap<Url, Vec<TextEdit>> = collect_occurrences(doc, pos, &symbol, ctx)
+        .into_iter()
+        .map(|(uri, occurrences)| {
+            let edits = occurrences
+                .into_iter()
+                .map(|o| TextEdit::new(o.range, new_name.to_string()))
+                .collect();
+            (uri, edits)
+        })
+        .collect();
+
+    Ok(Some(WorkspaceEdit::new(changes)))
+}
+
+fn check_renamable(symbol: &Symbol) -> Result<(), String> {
+    match symbol {
+        Symbol::Function(name) if Embedding::is_engine_callback(name) => Err(format!(
+            "'{}' is called by the engine and cannot be renamed. The engine would not call a renamed callback anymore.",
+            name
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn is_valid_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c == '_' || c.is_ascii_alphabetic())
+        && chars.all(|c| c == '_' || c.is_ascii_alphanumeric())
+        && !KEYWORDS.contains(&name)
+}
+
+/// Collects the occurrences of the symbol in every script it may be visible in.
+fn collect_occurrences(
+    doc: &Document,
+    pos: Position,
+    symbol: &Symbol,
+    ctx: &HandlerContext,
+) -> Vec<(Url, Vec<Occurrence>)> {
+    let source = doc.source.as_bytes();
+    let root = doc.tree.root_node();
+    let script = collect_document_signatures(doc, ctx.project);
+    let declarations = match (symbol, &script) {
+        (Symbol::Function(name), Some(script)) => function_declarations(doc, pos, name, script, ctx),
+        _ => HashSet::new(),
+    };
+
+    let mut result = match symbol {
+        Symbol::FunctionVariable { name, function } => {
+            let function = root.descendant_for_byte_range(function.start, function.end);
+            let occurrences = function
+                .map(|f| collect_variable_occurrences(&f, name, source, false))
+                .unwrap_or_default();
+            return vec![(doc.url.clone(), occurrences)];
+        }
+        Symbol::Function(name) => {
+            let Some(script) = &script else {
+                return vec![];
+            };
+            let occurrences = collect_function_occurrences(&root, name, source, script, &declarations, ctx);
+            vec![(doc.url.clone(), occurrences)]
+        }
+        Symbol::ScriptVariable { name, .. } => {
+            vec![(doc.url.clone(), collect_variable_occurrences(&root, name, source, true))]
+        }
+    };
+
+    for path in visible_scripts(doc, symbol, ctx) {
+        let Ok(uri) = Url::from_file_path(&path) else {
+            continue;
+        };
+
+        if uri == doc.url {
+            continue;
+        }
+
+        let open_doc = ctx.documents.get(&uri).map(|d| d.clone());
+        let (tree, source) = match open_doc {
+            Some(d) => (d.tree, d.source),
+            None => match parse_script(&path, symbol.name()) {
+                Some(parsed) => parsed,
+                None => continue,
+            },
+        };
+
+        let root = tree.root_node();
+        let occurrences = match symbol {
+            Symbol::Function(name) => match ctx.project.get(&path) {
+                Some(script) => collect_function_occurrences(&root, name, source.as_bytes(), script, &declarations, ctx),
+                None => continue,
+            },
+            _ => collect_variable_occurrences(&root, symbol.name(), source.as_bytes(), true),
+        };
+
+        if !occurrences.is_empty() {
+            result.push((uri, occurrences));
+        }
+    }
+
+    result.retain(|(_, occurrences)| !occurrences.is_empty());
+    result
+}
+
+/// Scripts (other than the document itself), which may reference the symbol.
+fn visible_scripts(doc: &Document, symbol: &Symbol, ctx: &HandlerContext) -> Vec<PathBuf> {
+    let project = ctx.project;
+
+    match symbol {
+        Symbol::FunctionVariable { .. } => vec![],
+        Symbol::Function(_) | Symbol::ScriptVariable { is_static: true, .. } => project
+            .files()
+            .map(|f| f.file_id.path.as_ref().clone())
+            .collect(),
+        Symbol::ScriptVariable { is_static: false, .. } => {
+            // locals are visible in the definition and every definition including it
+            let Some(id) = collect_document_signatures(doc, project).and_then(|s| s.definition_id) else {
+                return vec![];
+            };
+
+            project
+                .files()
+                .filter(|f| {
+                    f.definition_id.as_deref() == Some(id.as_str())
+                        || f.appendto.as_deref() == Some(id.as_str())
+                        || f.includes.contains(&id)
+                })
+                .map(|f| f.file_id.path.as_ref().clone())
+                .collect()
+        }
+    }
+}
+
+/// Parses a script from disk, if it mentions the name at all.
+fn parse_script(path: &PathBuf, name: &str) -> Option<(Tree, String)> {
+    let content = fs::read(path).ok()?;
+    let source = String::from_utf8_lossy(&content).into_owned();
+
+    if !source.contains(name) {
+        return None;
+    }
+
+    let tree = DocType::Script.get_parser().ok()?.parse(&source, None)?;
+    Some((tree, source))
+}
+
+/// Scripts declaring the function referenced at the position: the scripts it resolves to
+/// and the scripts overloading it along their script chains. Empty for engine functions.
+fn function_declarations(
+    doc: &Document,
+    pos: Position,
+    name: &str,
+    script: &FileSignatures,
+    ctx: &HandlerContext,
+) -> HashSet<FileId> {
+    let Some(fn_ref) = doc
+        .get_named_node_at(pos)
+        .and_then(|node| FunctionRef::from_node(&node, doc.source.as_bytes()))
+    else {
+        return HashSet::new();
+    };
+
+    let project = ctx.project;
+    fn_ref
+        .resolve(project, script)
+        .into_iter()
+        .flat_map(|(file, _)| project.script_chain(file))
+        .filter(|file| file.get_signature(name).is_some())
+        .map(|file| file.file_id.clone())
+        .collect()
+}
+
+/// Collects the declarations in `script` and the calls, which resolve to
+/// one of the `declarations`. Calls of engine functions match each other.
+fn collect_function_occurrences(
+    root: &Node,
+    name: &str,
+    source: &[u8],
+    script: &FileSignatures,
+    declarations: &HashSet<FileId>,
+    ctx: &HandlerContext,
+) -> Vec<Occurrence> {
+    let refers_to_declarations = |parent: &Node| {
+        if parent.kind() == NODE_KIND_FN_DEF {
+            return declarations.contains(&script.file_id);
+        }
+
+        let Some(fn_ref) = FunctionRef::from_call(parent, source) else {
+            return false;
+        };
+        let matches = fn_ref.resolve(ctx.project, script);
+        if declarations.is_empty() {
+            matches.is_empty()
+        } else {
+            matches.iter().any(|(file, _)| declarations.contains(&file.file_id))
+        }
+    };
+
+    let mut occurrences = vec![];
+    walk_identifiers(root, name, source, &mut |node, parent| {
+        if is_function_name(node, parent) && refers_to_declarations(parent) {
+            occurrences.push(Occurrence {
+                range: node_range(node, source),
+                start_byte: node.start_byte(),
+                is_declaration: parent.kind() == NODE_KIND_FN_DEF,
+            });
+        }
+        true
+    });
+
+    occurrences
+}
+
+/// Collects usages and declarations of a variable below `scope`.
+/// If `skip_shadowing_functions` is set, functions declaring a variable
+/// of the same name are skipped, as the name refers to their own variable there.
+fn collect_variable_occurrences(
+    scope: &Node,
+    name: &str,
+    source: &[u8],
+    skip_shadowing_functions: bool,
+) -> Vec<Occurrence> {
+    let mut occurrences = vec![];
+    walk_identifiers(scope, name, source, &mut |node, parent| {
+        if is_variable(node, parent) {
+            occurrences.push(Occurrence {
+                range: node_range(node, source),
+                start_byte: node.start_byte(),
+                is_declaration: is_declaration(node, parent),
+            });
+        }
+        true
+    });
+
+    if skip_shadowing_functions {
+        let mut cursor = scope.walk();
+        let shadowing: Vec<std::ops::Range<usize>> = scope
+            .named_children(&mut cursor)
+            .filter(|c| c.kind() == NODE_KIND_FN_DEF && declares_variable(c, name, source))
+            .map(|c| c.byte_range())
+            .collect();
+
+        occurrences.retain(|o| !shadowing.iter().any(|r| r.contains(&o.start_byte)));
+    }
+
+    occurrences
+}
+
+/// Calls `f` for every identifier with the given name below `node`,
+/// until `f` returns `false`. Returns `false` if the walk was stopped.
+fn walk_identifiers(node: &Node, name: &str, source: &[u8], f: &mut dyn FnMut(&Node, &Node) -> bool) -> bool {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        let proceed = if child.kind() == NODE_KIND_IDENTIFIER {
+            child.utf8_text(source).ok() != Some(name) || f(&child, node)
+        } else {
+            walk_identifiers(&child, name, source, f)
+        };
+
+        if !proceed {
+            return false;
+        }
+    }
+
+    true
+}
+
+fn is_function_name(node: &Node, parent: &Node) -> bool {
+    matches!(parent.kind(), NODE_KIND_FN_DEF | NODE_KIND_METHOD_CALL)
+        && parent.child_by_field_name("name") == Some(*node)
+}
+
+fn enclosing_function<'a>(node: &Node<'a>) -> Option<Node<'a>> {
+    let mut current = node.parent();
+    while let Some(n) = current {
+        if n.kind() == NODE_KIND_FN_DEF {
+            return Some(n);
+        }
+        current = n.parent();
+    }
+    None
+}
+
+/// Whether the function declares a parameter or `var` of the given name.
+fn declares_variable(function: &Node, name: &str, source: &[u8]) -> bool {
+    let mut found = false;
+    walk_identifiers(function, name, source, &mut |node, parent| {
+        found = match parent.kind() {
+            NODE_KIND_PARAM => parent.child_by_field_name("name") == Some(*node),
+            NODE_KIND_VAR_ASSIGNMENT => {
+                parent.child(0) == Some(*node)
+                    && parent.parent().is_some_and(|d| var_scope_of(&d, source) == Some("var"))
+            }
+            NODE_KIND_FOR_STATEMENT => is_declaration(node, parent),
+            _ => false,
+        };
+        !found
+    });
+
+    found
+}
+
+/// Finds a `local` or `static` declaration on script level.
+fn script_variable(root: Node, name: &str, source: &[u8]) -> Option<Symbol> {
+    let mut cursor = root.walk();
+    for definition in root.named_children(&mut cursor) {
+        let scope = match var_scope_of(&definition, source) {
+            Some(scope @ ("local" | "static")) => scope,
+            _ => continue,
+        };
+
+        let mut def_cursor = definition.walk();
+        let declares = definition
+            .named_children(&mut def_cursor)
+            .filter(|c| c.kind() == NODE_KIND_VAR_ASSIGNMENT)
+            .any(|a| a.child(0).and_then(|n| n.utf8_text(source).ok()) == Some(name));
+
+        if declares {
+            return Some(Symbol::ScriptVariable {
+                name: name.to_string(),
+                is_static: scope == "static",
+            });
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::{core::project::Project, lsp::asset_handler::TestContext};
+
+    fn create_doc(source: &str) -> Document {
+        let tree = DocType::Script.get_parser().unwrap().parse(source, None).unwrap();
+        Document::new(Url::parse("file:///Obj.c4d/Script.c").unwrap(), tree, source.to_string(), DocType::Script)
+    }
+
+    #[test]
+    fn should_resolve_symbols() {
+        let doc = create_doc("local count;\nfunc Foo(int count) {\n  return count;\n}\nfunc Bar() {\n  count++;\n  Foo(1);\n}");
+
+        assert!(matches!(symbol_at(&doc, Position::new(2, 10)), Some((Symbol::FunctionVariable { .. }, _))));
+        assert!(matches!(
+            symbol_at(&doc, Position::new(5, 3)),
+            Some((Symbol::ScriptVariable { is_static: false, .. }, _))
+        ));
+        assert!(matches!(symbol_at(&doc, Position::new(6, 3)), Some((Symbol::Function(_), _))));
+    }
+
+    #[test]
+    fn should_skip_shadowed_script_variables() {
+        let doc = create_doc("local count;\nfunc Foo(int count) {\n  return count;\n}\nfunc Bar() {\n  count++;\n}");
+        let root = doc.tree.root_node();
+
+        let occurrences = collect_variable_occurrences(&root, "count", doc.source.as_bytes(), true);
+        assert_eq!(occurrences.len(), 2);
+        assert!(occurrences[0].is_declaration);
+        assert_eq!(occurrences[1].range.start, Position::new(5, 2));
+    }
+
+    #[test]
+    fn should_rename_functions_of_the_resolved_declaration() {
+        let root = std::env::temp_dir().join(format!("c4-rename-test-{}", std::process::id()));
+        for (folder, id, script) in [
+            ("Door.c4d", "DOOR", "func Open() {}\nfunc Close() { Open(); }\n"),
+            ("Lock.c4d", "LOCK", "#include DOOR\nfunc Lock() { Open(); }\n"),
+            ("Box.c4d", "BOXX", "func Open() {}\nfunc Shake() { Open(); }\n"),
+        ] {
+            let def = root.join(folder);
+            fs::create_dir_all(&def).unwrap();
+            fs::write(def.join("DefCore.txt"), format!("[DefCore]\nid={}\n", id)).unwrap();
+            fs::write(def.join("Script.c"), script).unwrap();
+        }
+
+        let mut project = Project::new(vec![root.clone()]);
+        project.index_roots();
+        let context = TestContext::with_project(project);
+        let ctx = context.ctx();
+
+        let path = root.join("Door.c4d").join("Script.c");
+        let source = fs::read_to_string(&path).unwrap();
+        let tree = DocType::Script.get_parser().unwrap().parse(&source, None).unwrap();
+        let doc = Document::new(Url::from_file_path(&path).unwrap(), tree, source, DocType::Script);
+
+        let edit = rename(&doc, Position::new(0, 6), "Unlock", &ctx).unwrap().unwrap();
+        let mut files: Vec<(String, usize)> = edit
+            .changes
+            .unwrap()
+            .into_iter()
+            .map(|(uri, edits)| (uri.path().rsplit('/').nth(1).unwrap().to_string(), edits.len()))
+            .collect();
+        files.sort();
+        assert_eq!(files, vec![(String::from("Door.c4d"), 2), (String::from("Lock.c4d"), 1)]);
+
+        assert!(rename(&doc, Position::new(0, 6), "return", &ctx).is_err());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn should_refuse_renaming_engine_callbacks() {
+        let doc = create_doc("func Initialize() {}");
+        assert!(prepare_rename(&doc, Position::new(0, 7)).is_err());
+    }
+}
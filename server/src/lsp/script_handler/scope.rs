@@ -0,0 +1,289 @@
+use std::collections::HashMap;
+
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, Range};
+use tree_sitter::{Node, Tree};
+
+use crate::{
+    core::{
+        embedding::Embedding,
+        kind::{NODE_KIND_FN_DEF, NODE_KIND_IDENTIFIER, NODE_KIND_PARAM, NODE_KIND_VAR_ASSIGNMENT},
+        parse::{is_declaration, is_variable, node_range, var_scope_of, FileId, KEYWORDS},
+        project::Project,
+        signatures::{FileSignatures, VarScope, Variable},
+    },
+    lsp::diagnostics::create_diagnostic,
+};
+
+use super::analysis::ScriptAnalysis;
+
+/*
+Variables of a function are function wide, like in the engine:
+a `var` may be used before (or outside of the block of) its declaration.
+Names are resolved in this order:
+
+    parameters and `var`s of the enclosing function
+    `local`s and `static`s of the script, its definition and its includes
+    `static`s of any other script
+    engine constants
+*/
+
+/// What an identifier refers to
+#[derive(Debug, Clone)]
+pub enum Binding {
+    /// Parameter of the enclosing function
+    Param { declaration: Range, data_type: Option<String> },
+    /// `var` of the enclosing function, with its first declaration
+    Var { declaration: Range },
+    /// `local` or `static` declared on script level
+    Script { file_id: FileId, variable: Variable },
+    /// Constant of the engine
+    Constant,
+}
+
+/// An identifier used as expression, e.g. `count` in `count++`
+#[derive(Debug)]
+pub struct Reference<'t> {
+    pub node: Node<'t>,
+    pub name: &'t str,
+    /// `None` for undeclared identifiers
+    pub binding: Option<Binding>,
+}
+
+pub struct ScopeResolver<'a> {
+    /// Script variables visible in the script by their name
+    variables: HashMap<&'a str, (&'a FileSignatures, &'a Variable)>,
+    embedding: &'a Embedding,
+    complete: bool,
+}
+
+impl<'a> ScopeResolver<'a> {
+    pub fn new(script: &'a FileSignatures, project: &'a Project, embedding: &'a Embedding) -> Self {
+        let chain = project.script_chain(script);
+        let mut variables = HashMap::new();
+
+        // the nearest declaration wins
+        for file in chain.iter() {
+            for var in file.variables.iter() {
+                variables.entry(var.name.as_str()).or_insert((*file, var));
+            }
+        }
+
+        for file in project.files() {
+            for var in file.variables.iter().filter(|v| v.scope == VarScope::Static) {
+                variables.entry(var.name.as_str()).or_insert((file, var));
+            }
+        }
+
+        let complete = chain.iter().all(|file| {
+            file.includes
+                .iter()
+                .chain(file.appendto.iter().filter(|a| *a != "*"))
+                .all(|id| project.definition(id).is_some())
+        });
+
+        ScopeResolver {
+            variables,
+            embedding,
+            complete,
+        }
+    }
+
+    /// Whether every included definition is part of the project. Otherwise,
+    /// their script variables are unknown and identifiers may not resolve.
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    /// Resolves every identifier used as expression in the script.
+    pub fn resolve<'t>(&self, tree: &'t Tree, source: &'t str) -> Vec<Reference<'t>> {
+        let mut references = vec![];
+        let root = tree.root_node();
+
+        let mut cursor = root.walk();
+        for definition in root.named_children(&mut cursor) {
+            let locals = match definition.kind() {
+                NODE_KIND_FN_DEF => function_variables(&definition, source.as_bytes()),
+                _ => HashMap::new(),
+            };
+
+            self.resolve_node(&definition, &locals, source, &mut references);
+        }
+
+        references
+    }
+
+    fn resolve_node<'t>(
+        &self,
+        node: &Node<'t>,
+        locals: &HashMap<&str, Binding>,
+        source: &'t str,
+        references: &mut Vec<Reference<'t>>,
+    ) {
+        let mut cursor = node.walk();
+        for child in node.named_children(&mut cursor) {
+            if child.kind() != NODE_KIND_IDENTIFIER {
+                self.resolve_node(&child, locals, source, references);
+                continue;
+            }
+
+            if node.is_error() || !is_variable(&child, node) || is_declaration(&child, node) {
+                continue;
+            }
+
+            let Ok(name) = child.utf8_text(source.as_bytes()) else {
+                continue;
+            };
+
+            // the grammar parses `this` as identifier
+            if KEYWORDS.contains(&name) {
+                continue;
+            }
+
+            let binding = locals.get(name).cloned().or_else(|| self.script_binding(name));
+            references.push(Reference {
+                node: child,
+                name,
+                binding,
+            });
+        }
+    }
+
+    fn script_binding(&self, name: &str) -> Option<Binding> {
+        if let Some((file, variable)) = self.variables.get(name) {
+            return Some(Binding::Script {
+                file_id: file.file_id.clone(),
+                variable: (*variable).clone(),
+            });
+        }
+
+        self.embedding.get_constant(name).map(|_| Binding::Constant)
+    }
+}
+
+/// Warns about identifiers, which are neither declared nor engine constants.
+/// Nothing is reported for scripts including definitions outside of the workspace.
+pub fn undefined_identifiers(analysis: &ScriptAnalysis) -> Vec<Diagnostic> {
+    if !analysis.resolver.is_complete() {
+        return vec![];
+    }
+
+    let source = analysis.doc.source.as_bytes();
+    let embedding = analysis.ctx.embedding;
+    analysis
+        .references
+        .iter()
+        .filter(|r| r.binding.is_none() && !embedding.is_engine_constant(r.name))
+        .map(|r| {
+            create_diagnostic(
+                node_range(&r.node, source),
+                DiagnosticSeverity::WARNING,
+                format!("unknown identifier '{}'", r.name),
+            )
+        })
+        .collect()
+}
+
+/// Parameters and `var`s declared anywhere in the function
+pub fn function_variables<'t>(function: &Node<'t>, source: &'t [u8]) -> HashMap<&'t str, Binding> {
+    let mut variables = HashMap::new();
+    collect_function_variables(function, source, &mut variables);
+    variables
+}
+
+fn collect_function_variables<'t>(node: &Node<'t>, source: &'t [u8], variables: &mut HashMap<&'t str, Binding>) {
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        let binding = match child.kind() {
+            NODE_KIND_PARAM => child.child_by_field_name("name").map(|name| {
+                let data_type = child
+                    .child_by_field_name("type")
+                    .and_then(|t| t.utf8_text(source).ok())
+                    .map(String::from);
+                let binding = Binding::Param {
+                    declaration: node_range(&name, source),
+                    data_type,
+                };
+                (name, binding)
+            }),
+            NODE_KIND_VAR_ASSIGNMENT if var_scope_of(node, source) == Some("var") => child
+                .child(0)
+                .map(|name| (name, Binding::Var { declaration: node_range(&name, source) })),
+            NODE_KIND_IDENTIFIER if is_declaration(&child, node) => {
+                Some((child, Binding::Var { declaration: node_range(&child, source) }))
+            }
+            _ => {
+                collect_function_variables(&child, source, variables);
+                None
+            }
+        };
+
+        if let Some((name, binding)) = binding {
+            if let Ok(name) = name.utf8_text(source) {
+                variables.entry(name).or_insert(binding);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use tower_lsp::lsp_types::{Position, Url};
+
+    use super::*;
+    use crate::lsp::{
+        asset_handler::TestContext,
+        doc::{DocType, Document},
+        script_handler::{analysis::analyze, navigation::collect_document_signatures},
+    };
+
+    fn create_doc(source: &str) -> Document {
+        let tree = DocType::Script.get_parser().unwrap().parse(source, None).unwrap();
+        Document::new(Url::parse("file:///Obj.c4d/Script.c").unwrap(), tree, source.to_string(), DocType::Script)
+    }
+
+    #[test]
+    fn should_resolve_identifiers() {
+        let doc = create_doc(
+            "local count;\nstatic const Max = 3;\n\nfunc Foo(int amount, obj) {\n  for (var item in list) count += amount;\n  \
+             if (obj) { var later = C4D_Living; }\n  return later + missing + {key = Max}.key;\n}\n",
+        );
+        let context = TestContext::new();
+        let script = collect_document_signatures(&doc, &context.project).unwrap();
+
+        let resolver = ScopeResolver::new(&script, &context.project, &context.embedding);
+        assert!(resolver.is_complete());
+
+        let references = resolver.resolve(&doc.tree, &doc.source);
+        let find = |name: &str| references.iter().find(|r| r.name == name).unwrap();
+
+        assert_eq!(
+            references.iter().map(|r| r.name).collect::<Vec<&str>>(),
+            vec!["list", "count", "amount", "obj", "C4D_Living", "later", "missing", "Max"]
+        );
+        assert!(find("list").binding.is_none());
+        assert!(matches!(find("count").binding, Some(Binding::Script { ref variable, .. }) if variable.scope == VarScope::Local));
+        assert!(matches!(find("amount").binding, Some(Binding::Param { data_type: Some(ref t), .. }) if t == "int"));
+        assert!(matches!(find("obj").binding, Some(Binding::Param { data_type: None, .. })));
+        assert!(matches!(find("C4D_Living").binding, Some(Binding::Constant)));
+        assert!(matches!(find("later").binding, Some(Binding::Var { declaration }) if declaration.start == Position::new(5, 17)));
+        assert!(find("missing").binding.is_none());
+        assert!(matches!(find("Max").binding, Some(Binding::Script { .. })));
+    }
+
+    #[test]
+    fn should_warn_about_undefined_identifiers() {
+        let context = TestContext::new();
+        let check = |source: &str| analyze(&create_doc(source), &context.ctx(), undefined_identifiers).unwrap();
+
+        let diagnostics = check("func Foo() { return bar + this; }");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "unknown identifier 'bar'");
+
+        // constants of the engine, which are not documented yet
+        assert!(check("func Foo() { return OCF_Alive | COMD_Stop + NO_OWNER; }").is_empty());
+
+        // locals of the unknown definition may declare it
+        assert!(check("#include XYZW\nfunc Foo() { return bar; }").is_empty());
+    }
+}
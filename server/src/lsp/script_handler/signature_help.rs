@@ -0,0 +1,231 @@
+use tower_lsp::lsp_types::{
+    Documentation, ParameterInformation, ParameterLabel, Position, SignatureHelp, SignatureInformation,
+};
+use tree_sitter::Node;
+
+use crate::{
+    core::{embedding::FnHelp, kind::NODE_KIND_ARGS_LIST, signatures::{Param, Signature}},
+    lsp::{asset_handler::HandlerContext, doc::Document},
+};
+
+use super::{
+    completion::CompletionContext,
+    navigation::{collect_document_signatures, FunctionRef},
+};
+
+pub fn get_signature_help(doc: &Document, pos: Position, ctx: &HandlerContext) -> Option<SignatureHelp> {
+    let (byte, _) = doc.position_to_byte(pos);
+    let source = doc.source.as_bytes();
+
+    let (fn_ref, active_parameter) = match enclosing_args_list(doc, byte) {
+        Some(args) => (FunctionRef::from_call(&args.parent()?, source)?, active_parameter(&args, byte)),
+        // while typing, the call is usually incomplete and not parsed as `method_call` yet
+        None => scan_incomplete_call(&doc.source[..byte])?,
+    };
+
+    let script = collect_document_signatures(doc, ctx.project)?;
+    let user_signatures = fn_ref.resolve(ctx.project, &script);
+    let engine_signature = ctx.embedding.get_function(fn_ref.name());
+
+    let mut signatures: Vec<SignatureInformation> = user_signatures
+        .into_iter()
+        .map(|(_, sig)| from_signature(sig))
+        .collect();
+
+    // user functions overload engine functions of the same name
+    if signatures.is_empty() {
+        if let Some(help) = engine_signature {
+            signatures.push(from_fn_help(fn_ref.name(), help, active_parameter));
+        }
+    }
+
+    if signatures.is_empty() {
+        return None;
+    }
+
+    Some(SignatureHelp {
+        signatures,
+        active_signature: Some(0),
+        active_parameter: Some(active_parameter),
+    })
+}
+
+/// Innermost `args_list` the cursor is inside of, i.e. behind its `(`
+/// and in front of its `)` (if the list is closed at all).
+fn enclosing_args_list<'a>(doc: &'a Document, byte: usize) -> Option<Node<'a>> {
+    let mut node = doc.tree.root_node().descendant_for_byte_range(byte, byte);
+
+    while let Some(n) = node {
+        if n.kind() == NODE_KIND_ARGS_LIST {
+            let after_open = n.start_byte() < byte;
+            let before_close = match n.child(n.child_count().saturating_sub(1)) {
+                Some(last) if last.kind() == ")" && !last.is_missing() => byte <= last.start_byte(),
+                _ => true,
+            };
+
+            if after_open && before_close {
+                return Some(n);
+            }
+        }
+
+        node = n.parent();
+    }
+
+    None
+}
+
+fn active_parameter(args: &Node, byte: usize) -> u32 {
+    let mut cursor = args.walk();
+    args.children(&mut cursor)
+        .filter(|c| c.kind() == "," && c.end_byte() <= byte)
+        .count() as u32
+}
+
+/// Finds the call the end of `text` is inside of by scanning backwards
+/// for the unclosed `(`, counting the top level `,` on the way.
+fn scan_incomplete_call(text: &str) -> Option<(FunctionRef, u32)> {
+    let mut depth = 0;
+    let mut commas = 0;
+    let mut open_paren = None;
+
+    for (idx, c) in text.char_indices().rev() {
+        match c {
+            ')' | ']' | '}' => depth += 1,
+            '(' | '[' | '{' if depth > 0 => depth -= 1,
+            '(' => {
+                open_paren = Some(idx);
+                break;
+            }
+            '[' | '{' | ';' => return None,
+            ',' if depth == 0 => commas += 1,
+            _ => {}
+        }
+    }
+
+    let before = text[..open_paren?].trim_end();
+    let name_start = before
+        .rfind(|c: char| c != '_' && !c.is_ascii_alphanumeric())
+        .map_or(0, |i| i + 1);
+    let name = &before[name_start..];
+
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let name = name.to_string();
+    let fn_ref = match CompletionContext::from_line_prefix(&before[line_start..]) {
+        CompletionContext::Qualified(id) => FunctionRef::Qualified { id, name },
+        CompletionContext::OnObject => FunctionRef::OnObject { name },
+        CompletionContext::Unqualified => FunctionRef::Unqualified { name },
+    };
+
+    Some((fn_ref, commas))
+}
+
+/// Builds the signature label and the offsets of every parameter inside of it.
+fn build_label(name: &str, params: Vec<String>) -> (String, Vec<ParameterInformation>) {
+    let mut label = format!("func {}(", name);
+    let mut infos = vec![];
+
+    for (idx, param) in params.into_iter().enumerate() {
+        if idx > 0 {
+            label.push_str(", ");
+        }
+
+        let start = label.encode_utf16().count() as u32;
+        label.push_str(&param);
+        let end = label.encode_utf16().count() as u32;
+
+        infos.push(ParameterInformation {
+            label: ParameterLabel::LabelOffsets([start, end]),
+            documentation: None,
+        });
+    }
+
+    label.push(')');
+    (label, infos)
+}
+
+fn from_fn_help(name: &str, help: &FnHelp, active_parameter: u32) -> SignatureInformation {
+    let params = help.params.iter().map(|p| p.to_label()).collect();
+    let (label, mut parameters) = build_label(name, params);
+
+    for (info, param) in parameters.iter_mut().zip(help.params.iter()) {
+        info.documentation = param.description.clone().map(Documentation::String);
+    }
+
+    // all further arguments belong to a trailing `...`
+    let variadic_idx = help.params.last().filter(|p| p.is_variadic()).map(|_| help.params.len() as u32 - 1);
+    let active_parameter = variadic_idx.map(|idx| active_parameter.min(idx));
+
+    SignatureInformation {
+        label,
+        documentation: help.description.clone().map(Documentation::String),
+        parameters: Some(parameters),
+        active_parameter,
+    }
+}
+
+fn from_signature(sig: &Signature) -> SignatureInformation {
+    let params = sig.params.iter().map(Param::to_label).collect();
+    let (label, parameters) = build_label(&sig.name, params);
+
+    SignatureInformation {
+        label,
+        documentation: sig.doc.clone().map(Documentation::String),
+        parameters: Some(parameters),
+        active_parameter: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use tower_lsp::lsp_types::Url;
+
+    use super::*;
+    use crate::lsp::doc::DocType;
+
+    fn create_doc(source: &str) -> Document {
+        let tree = DocType::Script.get_parser().unwrap().parse(source, None).unwrap();
+        Document::new(Url::parse("file:///Script.c").unwrap(), tree, source.to_string(), DocType::Script)
+    }
+
+    #[test]
+    fn should_compute_active_parameter() {
+        let source = "func Foo() { Bar(1, Baz(2), 3); }";
+        let doc = create_doc(source);
+
+        let byte = source.find("3)").unwrap();
+        let args = enclosing_args_list(&doc, byte).unwrap();
+        assert_eq!(args.parent().unwrap().child_by_field_name("name").unwrap().utf8_text(source.as_bytes()).unwrap(), "Bar");
+        assert_eq!(active_parameter(&args, byte), 2);
+
+        let byte = source.find("2)").unwrap();
+        let args = enclosing_args_list(&doc, byte).unwrap();
+        assert_eq!(args.parent().unwrap().child_by_field_name("name").unwrap().utf8_text(source.as_bytes()).unwrap(), "Baz");
+        assert_eq!(active_parameter(&args, byte), 0);
+    }
+
+    #[test]
+    fn should_scan_incomplete_calls() {
+        let (fn_ref, active) = scan_incomplete_call("func F() {\n  CLNK::Bar(1, [2, 3], ").unwrap();
+        assert!(matches!(fn_ref, FunctionRef::Qualified { ref id, ref name } if id == "CLNK" && name == "Bar"));
+        assert_eq!(active, 2);
+
+        let (fn_ref, active) = scan_incomplete_call("  obj->~Foo(").unwrap();
+        assert!(matches!(fn_ref, FunctionRef::OnObject { ref name } if name == "Foo"));
+        assert_eq!(active, 0);
+
+        assert!(scan_incomplete_call("  Foo(1); x = ").is_none());
+    }
+
+    #[test]
+    fn should_label_parameters_with_offsets() {
+        let (label, params) = build_label("Foo", vec![String::from("int a"), String::from("b")]);
+
+        assert_eq!(label, "func Foo(int a, b)");
+        assert!(matches!(params[1].label, ParameterLabel::LabelOffsets([16, 17])));
+    }
+}
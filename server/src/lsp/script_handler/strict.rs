@@ -0,0 +1,87 @@
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity};
+use tree_sitter::Node;
+
+use crate::{
+    core::{
+        kind::NODE_KIND_BINARY_EXPRESSION,
+        parse::node_range,
+        signatures::StrictMode,
+    },
+    lsp::diagnostics::create_diagnostic,
+};
+
+use super::analysis::ScriptAnalysis;
+
+/// Operators comparing strings, which `==` and `!=` replace from `#strict 2` on
+const STRING_OPERATORS: [(&str, &str); 3] = [("S=", "=="), ("eq", "=="), ("ne", "!=")];
+
+/// Reports constructs deprecated or not allowed in the `#strict` level of the script.
+pub fn check_strict_mode(analysis: &ScriptAnalysis) -> Vec<Diagnostic> {
+    let (doc, strict) = (analysis.doc, analysis.script.strict);
+
+    let mut diagnostics = vec![];
+    if strict.deprecates_string_operators() {
+        check_operators(&doc.tree.root_node(), strict, doc.source.as_bytes(), &mut diagnostics);
+    }
+    diagnostics
+}
+
+fn check_operators(node: &Node, strict: StrictMode, source: &[u8], diagnostics: &mut Vec<Diagnostic>) {
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if node.kind() == NODE_KIND_BINARY_EXPRESSION && !child.is_named() {
+            if let Some((op, replacement)) = STRING_OPERATORS.iter().find(|(op, _)| *op == child.kind()) {
+                let (severity, verdict) = match strict.forbids_string_operators() {
+                    true => (DiagnosticSeverity::ERROR, "not allowed"),
+                    false => (DiagnosticSeverity::WARNING, "deprecated"),
+                };
+
+                diagnostics.push(create_diagnostic(
+                    node_range(&child, source),
+                    severity,
+                    format!("'{}' is {} in {}, use '{}' instead", op, verdict, strict.moniker(), replacement),
+                ));
+            }
+        }
+
+        check_operators(&child, strict, source, diagnostics);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use tower_lsp::lsp_types::Url;
+
+    use super::*;
+    use crate::lsp::{
+        asset_handler::TestContext,
+        doc::{DocType, Document},
+        script_handler::analysis::analyze,
+    };
+
+    fn check(source: &str) -> Vec<Diagnostic> {
+        let tree = DocType::Script.get_parser().unwrap().parse(source, None).unwrap();
+        let doc = Document::new(Url::parse("file:///Obj.c4d/Script.c").unwrap(), tree, source.to_string(), DocType::Script);
+
+        let context = TestContext::new();
+        analyze(&doc, &context.ctx(), check_strict_mode).unwrap()
+    }
+
+    #[test]
+    fn should_report_string_operators_by_strict_mode() {
+        let body = "func Foo(a, b) {\n  return a eq b || a S= b;\n}\n";
+
+        assert!(check(body).is_empty());
+        assert!(check(&format!("#strict\n{}", body)).is_empty());
+
+        let diagnostics = check(&format!("#strict 2\n{}", body));
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].message, "'eq' is deprecated in #strict 2, use '==' instead");
+        assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::WARNING));
+
+        let diagnostics = check(&format!("#strict 3\n{}", body));
+        assert_eq!(diagnostics[1].message, "'S=' is not allowed in #strict 3, use '==' instead");
+        assert_eq!(diagnostics[1].severity, Some(DiagnosticSeverity::ERROR));
+    }
+}
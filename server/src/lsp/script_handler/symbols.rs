@@ -0,0 +1,139 @@
+use tower_lsp::lsp_types::{DocumentSymbol, SymbolKind};
+use tree_sitter::Node;
+
+use crate::{
+    core::{
+        kind::{
+            NODE_KIND_APPENDTO, NODE_KIND_FN_DEF, NODE_KIND_INCLUDE, NODE_KIND_PARAM, NODE_KIND_VAR_ASSIGNMENT,
+            NODE_KIND_VAR_DEFINITION,
+        },
+        parse::node_range,
+    },
+    lsp::{doc::Document, symbols::create_symbol},
+};
+
+/// Outline of a script: directives, script variables and functions in source order.
+pub fn get_document_symbols(doc: &Document) -> Vec<DocumentSymbol> {
+    let source = doc.source.as_bytes();
+    let root = doc.tree.root_node();
+    let mut cursor = root.walk();
+    let mut symbols = vec![];
+
+    for node in root.named_children(&mut cursor) {
+        match node.kind() {
+            NODE_KIND_INCLUDE | NODE_KIND_APPENDTO => {
+                if let Some(symbol) = directive_symbol(&node, source) {
+                    symbols.push(symbol);
+                }
+            }
+            NODE_KIND_VAR_DEFINITION => symbols.append(&mut variable_symbols(&node, source)),
+            NODE_KIND_FN_DEF => {
+                if let Some(symbol) = function_symbol(&node, source) {
+                    symbols.push(symbol);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    symbols
+}
+
+fn directive_symbol(node: &Node, source: &[u8]) -> Option<DocumentSymbol> {
+    let id = node.child(1)?;
+    let directive = node.child(0)?.utf8_text(source).ok()?;
+
+    Some(create_symbol(
+        id.utf8_text(source).ok()?.to_string(),
+        Some(directive.to_string()),
+        SymbolKind::MODULE,
+        node_range(node, source),
+        node_range(&id, source),
+        None,
+    ))
+}
+
+fn variable_symbols(node: &Node, source: &[u8]) -> Vec<DocumentSymbol> {
+    let scope = match node.child(0).and_then(|n| n.utf8_text(source).ok()) {
+        Some(scope @ ("local" | "static")) => scope,
+        _ => return vec![],
+    };
+    let is_const = node.child_by_field_name("const").is_some();
+
+    let (kind, detail) = match (scope, is_const) {
+        (_, true) => (SymbolKind::CONSTANT, format!("{} const", scope)),
+        ("local", _) => (SymbolKind::FIELD, String::from(scope)),
+        _ => (SymbolKind::VARIABLE, String::from(scope)),
+    };
+
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor)
+        .filter(|n| n.kind() == NODE_KIND_VAR_ASSIGNMENT)
+        .filter_map(|assignment| {
+            let name = assignment.child(0)?;
+            Some(create_symbol(
+                name.utf8_text(source).ok()?.to_string(),
+                Some(detail.clone()),
+                kind,
+                node_range(&assignment, source),
+                node_range(&name, source),
+                None,
+            ))
+        })
+        .collect()
+}
+
+fn function_symbol(node: &Node, source: &[u8]) -> Option<DocumentSymbol> {
+    let name = node.child_by_field_name("name")?;
+    let visibility = node
+        .child_by_field_name("visibility")
+        .and_then(|v| v.utf8_text(source).ok())
+        .map(|v| format!("{} ", v))
+        .unwrap_or_default();
+
+    let mut params = vec![];
+    let mut cursor = node.walk();
+    if let Some(list) = node.named_children(&mut cursor).find(|n| n.kind() == "parameter_list") {
+        let mut list_cursor = list.walk();
+        for param in list.named_children(&mut list_cursor).filter(|n| n.kind() == NODE_KIND_PARAM) {
+            if let Ok(text) = param.utf8_text(source) {
+                params.push(text);
+            }
+        }
+    }
+
+    Some(create_symbol(
+        name.utf8_text(source).ok()?.to_string(),
+        Some(format!("{}func({})", visibility, params.join(", "))),
+        SymbolKind::FUNCTION,
+        node_range(node, source),
+        node_range(&name, source),
+        None,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use tower_lsp::lsp_types::Url;
+
+    use super::*;
+    use crate::lsp::doc::DocType;
+
+    #[test]
+    fn should_collect_script_outline() {
+        let source = "#strict 2\n#include CLNK\n#appendto ROCK\nlocal a, b;\nstatic const c = 1;\nprivate func Foo(x, int y) { var z; }\nfunc Bar() {}\n";
+        let tree = DocType::Script.get_parser().unwrap().parse(source, None).unwrap();
+        let doc = Document::new(Url::parse("file:///Script.c").unwrap(), tree, source.to_string(), DocType::Script);
+
+        let symbols = get_document_symbols(&doc);
+        let names: Vec<&str> = symbols.iter().map(|s| s.name.as_str()).collect();
+
+        assert_eq!(names, vec!["CLNK", "ROCK", "a", "b", "c", "Foo", "Bar"]);
+        assert_eq!(symbols[0].detail.as_deref(), Some("#include"));
+        assert_eq!(symbols[2].kind, SymbolKind::FIELD);
+        assert_eq!(symbols[4].detail.as_deref(), Some("static const"));
+        assert_eq!(symbols[5].detail.as_deref(), Some("private func(x, int y)"));
+        assert_eq!(symbols[6].detail.as_deref(), Some("func()"));
+    }
+}
@@ -0,0 +1,275 @@
+use std::collections::{BTreeMap, HashMap};
+
+use tower_lsp::lsp_types::Position;
+use tree_sitter::Node;
+
+use crate::{
+    core::{
+        embedding::Embedding,
+        lint::ExpressionTypes,
+        kind::{
+            NODE_KIND_ARRAY, NODE_KIND_ARROW_EXPRESSION, NODE_KIND_BINARY_EXPRESSION,
+            NODE_KIND_BOOL, NODE_KIND_BUILTIN_CONSTANT, NODE_KIND_FOR_STATEMENT, NODE_KIND_ID,
+            NODE_KIND_IDENTIFIER, NODE_KIND_MAP, NODE_KIND_METHOD_CALL, NODE_KIND_NIL, NODE_KIND_NUMBER,
+            NODE_KIND_STRING, NODE_KIND_TRANSLATION, NODE_KIND_UNARY_EXPRESSION, NODE_KIND_VAR_ASSIGNMENT,
+        },
+        parse::{is_declaration, node_range, operator, var_scope_of},
+        project::Project,
+        signatures::{C4DataType, FileSignatures},
+    },
+    lsp::doc::Document,
+};
+
+use super::{
+    navigation::FunctionRef,
+    scope::{Binding, Reference, ScopeResolver},
+};
+
+/*
+Types are inferred flow-insensitively: a `var` has the type of all values
+assigned to it, or no type if they disagree. `nil` and `0` carry no type
+information, as legacy scripts use them as "no object" as well, but `0`
+alone still makes an `int`.
+*/
+
+/// Maximum number of passes, as `var`s may be typed by other `var`s
+const MAX_PASSES: usize = 4;
+
+/// Inferred types of the expressions and `var`s of a script
+pub struct TypeInference<'a> {
+    project: &'a Project,
+    embedding: &'a Embedding,
+    script: &'a FileSignatures,
+    source: &'a [u8],
+    /// Binding of every identifier used as expression by its start byte
+    bindings: HashMap<usize, Binding>,
+    /// Types of the `var`s by the start of their declaration
+    vars: BTreeMap<Position, C4DataType>,
+}
+
+/// Value assigned to a `var`, `None` for values of unknown type (e.g. the items of `for (var x in list)`)
+type Assignment<'t> = (Position, Option<Node<'t>>);
+
+#[derive(Default)]
+struct VarTypes {
+    /// `None` until a typed value is assigned, `Some(None)` for conflicting types
+    strong: Option<Option<C4DataType>>,
+    assigned_zero: bool,
+}
+
+impl<'a> TypeInference<'a> {
+    pub fn new(doc: &'a Document, script: &'a FileSignatures, project: &'a Project, embedding: &'a Embedding) -> Self {
+        let references = ScopeResolver::new(script, project, embedding).resolve(&doc.tree, &doc.source);
+        Self::from_references(doc, script, project, embedding, &references)
+    }
+
+    /// Infers the types based on the already resolved identifiers of the document.
+    pub fn from_references(
+        doc: &'a Document,
+        script: &'a FileSignatures,
+        project: &'a Project,
+        embedding: &'a Embedding,
+        references: &[Reference],
+    ) -> Self {
+        let bindings = references
+            .iter()
+            .filter_map(|r| r.binding.clone().map(|b| (r.node.start_byte(), b)))
+            .collect();
+
+        let mut inference = TypeInference {
+            project,
+            embedding,
+            script,
+            source: doc.source.as_bytes(),
+            bindings,
+            vars: BTreeMap::new(),
+        };
+
+        let mut assignments = vec![];
+        inference.collect_assignments(&doc.tree.root_node(), &mut assignments);
+
+        for _ in 0..MAX_PASSES {
+            let vars = inference.infer_vars(&assignments);
+            if vars == inference.vars {
+                break;
+            }
+            inference.vars = vars;
+        }
+
+        inference
+    }
+
+    /// Binding of an identifier used as expression
+    pub fn binding(&self, identifier: &Node) -> Option<&Binding> {
+        self.bindings.get(&identifier.start_byte())
+    }
+
+    pub fn binding_type(&self, binding: &Binding) -> Option<C4DataType> {
+        match binding {
+            Binding::Param { data_type, declaration } => match data_type {
+                Some(t) => C4DataType::from_moniker(t),
+                None => self.vars.get(&declaration.start).copied(),
+            },
+            Binding::Var { declaration } => self.vars.get(&declaration.start).copied(),
+            Binding::Script { .. } => None,
+            Binding::Constant => None,
+        }
+    }
+
+    /// Type of an expression, `None` if unknown
+    pub fn type_of(&self, node: &Node) -> Option<C4DataType> {
+        match node.kind() {
+            NODE_KIND_NUMBER => Some(C4DataType::Int),
+            NODE_KIND_STRING | NODE_KIND_TRANSLATION => Some(C4DataType::String),
+            NODE_KIND_BOOL => Some(C4DataType::Bool),
+            NODE_KIND_ID => Some(C4DataType::Id),
+            NODE_KIND_ARRAY => Some(C4DataType::Array),
+            NODE_KIND_MAP => Some(C4DataType::Map),
+            NODE_KIND_BUILTIN_CONSTANT => match node.utf8_text(self.source).ok()? {
+                "NO_OWNER" => Some(C4DataType::Int),
+                _ => None,
+            },
+            NODE_KIND_IDENTIFIER => match self.binding(node)? {
+                Binding::Constant => {
+                    let name = node.utf8_text(self.source).ok()?;
+                    self.embedding.get_constant(name).map(|c| c.data_type)
+                }
+                binding => self.binding_type(binding),
+            },
+            NODE_KIND_METHOD_CALL => self.return_type(node),
+            NODE_KIND_UNARY_EXPRESSION => match operator(node)? {
+                "!" => Some(C4DataType::Bool),
+                _ => Some(C4DataType::Int),
+            },
+            NODE_KIND_BINARY_EXPRESSION => match operator(node)? {
+                "==" | "!=" | "<" | "<=" | ">" | ">=" | "S=" | "eq" | "ne" | "&&" | "||" => Some(C4DataType::Bool),
+                "=" => self.type_of(&node.named_child(node.named_child_count().checked_sub(1)?)?),
+                "??" | "??=" | ".." | "..=" => None,
+                _ => Some(C4DataType::Int),
+            },
+            _ => None,
+        }
+    }
+
+    /// Return type of an engine function. Calls of script functions
+    /// (and on other objects) are of unknown type.
+    fn return_type(&self, call: &Node) -> Option<C4DataType> {
+        if call.parent().is_some_and(|p| p.kind() == NODE_KIND_ARROW_EXPRESSION) {
+            return None;
+        }
+
+        let fn_ref = FunctionRef::from_call(call, self.source)?;
+        if !fn_ref.resolve(self.project, self.script).is_empty() {
+            return None;
+        }
+
+        self.embedding.get_function(fn_ref.name())?.return_type
+    }
+
+    fn collect_assignments<'t>(&self, node: &Node<'t>, assignments: &mut Vec<Assignment<'t>>) {
+        let mut cursor = node.walk();
+        for child in node.named_children(&mut cursor) {
+            match child.kind() {
+                NODE_KIND_VAR_ASSIGNMENT if var_scope_of(node, self.source) == Some("var") => {
+                    if let (Some(name), Some(value)) = (child.child(0), child.named_child(1)) {
+                        assignments.push((node_range(&name, self.source).start, Some(value)));
+                    }
+                }
+                NODE_KIND_IDENTIFIER if node.kind() == NODE_KIND_FOR_STATEMENT && is_declaration(&child, node) => {
+                    assignments.push((node_range(&child, self.source).start, None));
+                }
+                NODE_KIND_BINARY_EXPRESSION if operator(&child) == Some("=") => {
+                    let target = child.named_child(0).filter(|t| t.kind() == NODE_KIND_IDENTIFIER);
+                    let declaration = target.and_then(|t| match self.binding(&t) {
+                        Some(Binding::Var { declaration }) => Some(declaration.start),
+                        Some(Binding::Param { data_type: None, declaration }) => Some(declaration.start),
+                        _ => None,
+                    });
+
+                    if let Some(declaration) = declaration {
+                        let value = child.named_child_count().checked_sub(1).and_then(|i| child.named_child(i));
+                        assignments.push((declaration, value));
+                    }
+                }
+                _ => {}
+            }
+
+            self.collect_assignments(&child, assignments);
+        }
+    }
+
+    fn infer_vars(&self, assignments: &[Assignment]) -> BTreeMap<Position, C4DataType> {
+        let mut vars: BTreeMap<Position, VarTypes> = BTreeMap::new();
+
+        for (declaration, value) in assignments {
+            let entry = vars.entry(*declaration).or_default();
+
+            let data_type = match value {
+                Some(v) if v.kind() == NODE_KIND_NIL => continue,
+                Some(v) if v.kind() == NODE_KIND_NUMBER && v.utf8_text(self.source).ok() == Some("0") => {
+                    entry.assigned_zero = true;
+                    continue;
+                }
+                Some(v) => self.type_of(v),
+                None => None,
+            };
+
+            entry.strong = match entry.strong {
+                None => Some(data_type),
+                Some(t) if t == data_type => Some(t),
+                Some(_) => Some(None),
+            };
+        }
+
+        vars.into_iter()
+            .filter_map(|(declaration, types)| {
+                let data_type = match types.strong {
+                    Some(t) => t,
+                    None => types.assigned_zero.then_some(C4DataType::Int),
+                };
+                data_type.map(|t| (declaration, t))
+            })
+            .collect()
+    }
+}
+
+impl ExpressionTypes for TypeInference<'_> {
+    fn type_of(&self, node: &Node) -> Option<C4DataType> {
+        TypeInference::type_of(self, node)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use tower_lsp::lsp_types::Url;
+
+    use super::*;
+    use crate::lsp::{asset_handler::TestContext, doc::DocType, script_handler::navigation::collect_document_signatures};
+
+    fn create_doc(source: &str) -> Document {
+        let tree = DocType::Script.get_parser().unwrap().parse(source, None).unwrap();
+        Document::new(Url::parse("file:///Obj.c4d/Script.c").unwrap(), tree, source.to_string(), DocType::Script)
+    }
+
+    #[test]
+    fn should_infer_var_types() {
+        let doc = create_doc(
+            "func Foo(int amount, target) {\n  var obj = CreateObject(ROCK);\n  var sum = amount * 2;\n  \
+             var copy = sum;\n  var mixed = 1;\n  mixed = \"text\";\n  var none = 0;\n  none = obj;\n  var ok = sum > 1;\n  \
+             target = [1];\n}\n",
+        );
+        let context = TestContext::new();
+        let script = collect_document_signatures(&doc, &context.project).unwrap();
+        let inference = TypeInference::new(&doc, &script, &context.project, &context.embedding);
+
+        let var_type = |line: u32, character: u32| inference.vars.get(&Position::new(line, character)).copied();
+        assert_eq!(var_type(1, 6), Some(C4DataType::Object));
+        assert_eq!(var_type(2, 6), Some(C4DataType::Int));
+        assert_eq!(var_type(3, 6), Some(C4DataType::Int));
+        assert_eq!(var_type(4, 6), None);
+        assert_eq!(var_type(6, 6), Some(C4DataType::Object));
+        assert_eq!(var_type(8, 6), Some(C4DataType::Bool));
+        assert_eq!(var_type(0, 21), Some(C4DataType::Array));
+    }
+}
@@ -0,0 +1,108 @@
+use tower_lsp::lsp_types::{DocumentSymbol, Range, SymbolKind};
+use tree_sitter::{Node, Tree};
+
+use crate::core::{
+    ini::node_kind::{NODE_KIND_PROPERTY, NODE_KIND_SECTION, NODE_KIND_SECTION_NAME},
+    parse::node_range,
+};
+
+#[allow(deprecated)]
+pub fn create_symbol(
+    name: String,
+    detail: Option<String>,
+    kind: SymbolKind,
+    range: Range,
+    selection_range: Range,
+    children: Option<Vec<DocumentSymbol>>,
+) -> DocumentSymbol {
+    DocumentSymbol {
+        name,
+        detail,
+        kind,
+        tags: None,
+        deprecated: None,
+        range,
+        selection_range,
+        children,
+    }
+}
+
+/// Sections of an ini file with their properties as children.
+pub fn collect_ini_symbols(tree: &Tree, source: &str) -> Vec<DocumentSymbol> {
+    let source = source.as_bytes();
+    let root = tree.root_node();
+    let mut cursor = root.walk();
+
+    root.named_children(&mut cursor)
+        .filter(|n| n.kind() == NODE_KIND_SECTION)
+        .filter_map(|section| section_symbol(&section, source))
+        .collect()
+}
+
+fn section_symbol(section: &Node, source: &[u8]) -> Option<DocumentSymbol> {
+    let mut cursor = section.walk();
+    let header = section.named_children(&mut cursor).find(|n| n.kind() == NODE_KIND_SECTION_NAME)?;
+    let name = header.named_child(0).unwrap_or(header);
+
+    let properties = section
+        .named_children(&mut cursor)
+        .filter(|n| n.kind() == NODE_KIND_PROPERTY)
+        .filter_map(|p| property_symbol(&p, source))
+        .collect();
+
+    Some(create_symbol(
+        name.utf8_text(source).ok()?.to_string(),
+        None,
+        SymbolKind::NAMESPACE,
+        node_range(section, source),
+        node_range(&name, source),
+        Some(properties),
+    ))
+}
+
+fn property_symbol(property: &Node, source: &[u8]) -> Option<DocumentSymbol> {
+    let key = property.child(0)?;
+    let name = key.utf8_text(source).ok()?.trim();
+    if name.is_empty() {
+        return None;
+    }
+
+    let value = property
+        .named_child(0)
+        .and_then(|v| v.utf8_text(source).ok())
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty());
+
+    Some(create_symbol(
+        name.to_string(),
+        value,
+        SymbolKind::PROPERTY,
+        node_range(property, source),
+        node_range(&key, source),
+        None,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::lsp::doc::DocType;
+
+    #[test]
+    fn should_collect_sections_with_properties() {
+        let source = "[Head]\nTitle=Foo\nIcon=1\n\n[Player1]\nCrew=CLNK=2;\n";
+        let tree = DocType::ScenarioTxt.get_parser().unwrap().parse(source, None).unwrap();
+
+        let symbols = collect_ini_symbols(&tree, source);
+
+        assert_eq!(symbols.len(), 2);
+        assert_eq!(symbols[0].name, "Head");
+        let properties = symbols[0].children.as_ref().unwrap();
+        assert_eq!(properties.len(), 2);
+        assert_eq!(properties[0].name, "Title");
+        assert_eq!(properties[0].detail.as_deref(), Some("Foo"));
+        assert_eq!(symbols[1].name, "Player1");
+        assert_eq!(symbols[1].children.as_ref().unwrap()[0].detail.as_deref(), Some("CLNK=2;"));
+    }
+}
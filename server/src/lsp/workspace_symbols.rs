@@ -0,0 +1,104 @@
+use std::{collections::HashSet, path::Path};
+
+use tower_lsp::lsp_types::{Location, Range, SymbolInformation, SymbolKind, Url};
+
+use crate::{
+    core::{
+        definitions::DEF_CORE_FILE_NAME,
+        fuzzy::fuzzy_score,
+        project::Project,
+        signatures::{FileSignatures, VarScope},
+    },
+    lsp::{asset_handler::HandlerContext, doc::DocType, script_handler::navigation::collect_document_signatures},
+};
+
+const MAX_WORKSPACE_SYMBOLS: usize = 256;
+
+/// Fuzzy searches functions, script variables and definition ids of all indexed scripts.
+/// Open documents are searched in their current state instead of their state on disk.
+pub fn get_workspace_symbols(query: &str, ctx: &HandlerContext) -> Vec<SymbolInformation> {
+    let mut candidates = vec![];
+    let mut definitions = HashSet::new();
+
+    let open_scripts = open_scripts(ctx);
+    let indexed = ctx
+        .project
+        .files()
+        .filter(|f| !open_scripts.iter().any(|o| o.file_id == f.file_id));
+
+    for file in indexed.chain(open_scripts.iter()) {
+        let Ok(uri) = Url::from_file_path(file.file_id.path.as_ref()) else {
+            continue;
+        };
+        let container = file.definition_id.clone();
+
+        for sig in file.signatures.iter() {
+            candidates.push(symbol(&sig.name, SymbolKind::FUNCTION, &uri, sig.name_range, container.clone()));
+        }
+
+        for var in file.variables.iter() {
+            let kind = match (var.scope, var.is_const) {
+                (_, true) => SymbolKind::CONSTANT,
+                (VarScope::Local, _) => SymbolKind::FIELD,
+                (VarScope::Static, _) => SymbolKind::VARIABLE,
+            };
+            candidates.push(symbol(&var.name, kind, &uri, var.range, container.clone()));
+        }
+
+        if let Some(ref id) = file.definition_id {
+            let folder = file.file_id.path.parent();
+            if !definitions.insert((id.clone(), folder.map(Path::to_path_buf))) {
+                continue;
+            }
+
+            let def_core = folder.map(|f| f.join(DEF_CORE_FILE_NAME));
+            if let Some(def_core_uri) = def_core.and_then(|p| Url::from_file_path(p).ok()) {
+                candidates.push(symbol(id, SymbolKind::CLASS, &def_core_uri, Range::default(), None));
+            }
+        }
+    }
+
+    let mut matches: Vec<(i64, SymbolInformation)> = candidates
+        .into_iter()
+        .filter_map(|s| fuzzy_score(query, &s.name).map(|score| (score, s)))
+        .collect();
+
+    matches.sort_by(|(a_score, a), (b_score, b)| b_score.cmp(a_score).then_with(|| a.name.cmp(&b.name)));
+    matches.truncate(MAX_WORKSPACE_SYMBOLS);
+    matches.into_iter().map(|(_, s)| s).collect()
+}
+
+/// Signatures of the open scripts, which belong into the index.
+fn open_scripts(ctx: &HandlerContext) -> Vec<FileSignatures> {
+    let mut files = vec![];
+
+    for doc in ctx.documents.iter() {
+        if doc.doc_type != DocType::Script {
+            continue;
+        }
+        let Ok(path) = doc.url.to_file_path() else {
+            continue;
+        };
+        if !Project::is_indexed_script(&path) {
+            continue;
+        }
+
+        if let Some(sigs) = collect_document_signatures(&doc, ctx.project) {
+            files.push(sigs);
+        }
+    }
+
+    files
+}
+
+#[allow(deprecated)]
+fn symbol(name: &str, kind: SymbolKind, uri: &Url, range: Range, container_name: Option<String>) -> SymbolInformation {
+    SymbolInformation {
+        name: name.to_string(),
+        kind,
+        tags: None,
+        deprecated: None,
+        location: Location::new(uri.clone(), range),
+        container_name,
+    }
+}
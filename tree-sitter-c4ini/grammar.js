@@ -7,17 +7,27 @@
 /// <reference types="tree-sitter-cli/dsl" />
 // @ts-check
 
-const idRe = /[_A-Z0-9]{4}/;
+// characters with a meaning inside of a value
+const special = '\\[\\]=,;|';
 
 module.exports = grammar({
   name: "c4ini",
 
+  // line breaks end properties, so they are not skipped
+  extras: $ => [/[ \t\r]/],
+
   rules: {
-    source_file: $ => repeat($.section),
+    source_file: $ => seq(
+      repeat(choice($.comment, $._newline)),
+      repeat($.section),
+    ),
 
     section: $ => seq(
       $.section_name,
-      repeat($.property),
+      repeat(seq(
+        $._newline,
+        optional(choice($.property, $.comment)),
+      )),
     ),
 
     section_name: $ => seq(
@@ -26,33 +36,49 @@ module.exports = grammar({
       ']',
     ),
 
-    property: $ => seq(
+    property: $ => prec.right(seq(
       alias($.identifier, 'property_key'),
       '=',
-      $.joined_value,
-      //$.property_values,
-    ),
+      optional($.value),
+    )),
 
-    joined_value: $ => token(/.+/),
+    // everything up to the end of the line, e.g. `1,2,3`, `C4D_Living|C4D_Object` or `CLNK=1;FLAG=2`
+    value: $ => prec.right(repeat1(choice(
+      $.pair,
+      $._atom,
+      ',',
+      ';',
+      '|',
+      '=',
+      '[',
+      ']',
+    ))),
 
-    property_values: $ => seq(
-      $.property_value,
-      repeat(seq(';', $.property_value)),
-    ),
+    // entry of an id or material list, e.g. `CLNK=1`
+    pair: $ => prec.right(1, seq(
+      $._atom,
+      '=',
+      optional($._atom),
+    )),
 
-    property_value: $ => choice(
-      $.identifier,
+    _atom: $ => choice(
       $.number,
-      $.id,
+      $.identifier,
+      $.text,
     ),
 
-    id: $ => idRe,
-
-    number: $ => choice(
-      /\d+/,
+    number: $ => token(choice(
+      /-?\d+/,
       /0x[0-9a-fA-F]+/,
-    ),
+    )),
 
     identifier: $ => /[_a-zA-Z][_a-zA-Z0-9]*/,
+
+    // anything else, e.g. `New Scenario` or `Fantasy.c4f\Alchemy.c4s`
+    text: $ => token(prec(-1, new RegExp(`[^${special}\\s]([^${special}\\n]*[^${special}\\s])?`))),
+
+    comment: $ => token(seq(choice(';', '#', '//'), /[^\n]*/)),
+
+    _newline: $ => /\n/,
   }
 });
@@ -6,11 +6,11 @@
 #endif
 
 #define LANGUAGE_VERSION 14
-#define STATE_COUNT 14
+#define STATE_COUNT 38
 #define LARGE_STATE_COUNT 2
-#define SYMBOL_COUNT 16
+#define SYMBOL_COUNT 23
 #define ALIAS_COUNT 1
-#define TOKEN_COUNT 10
+#define TOKEN_COUNT 12
 #define EXTERNAL_TOKEN_COUNT 0
 #define FIELD_COUNT 0
 #define MAX_ALIAS_SEQUENCE_LENGTH 3
@@ -20,19 +20,26 @@ enum {
   anon_sym_LBRACK = 1,
   anon_sym_RBRACK = 2,
   anon_sym_EQ = 3,
-  sym_joined_value = 4,
+  anon_sym_COMMA = 4,
   anon_sym_SEMI = 5,
-  sym_id = 6,
-  aux_sym_number_token1 = 7,
-  aux_sym_number_token2 = 8,
-  sym_identifier = 9,
-  sym_source_file = 10,
-  sym_section = 11,
-  sym_section_name = 12,
-  sym_property = 13,
-  aux_sym_source_file_repeat1 = 14,
-  aux_sym_section_repeat1 = 15,
-  anon_alias_sym_property_key = 16,
+  anon_sym_PIPE = 6,
+  sym_number = 7,
+  sym_identifier = 8,
+  sym_text = 9,
+  sym_comment = 10,
+  sym__newline = 11,
+  sym_source_file = 12,
+  sym_section = 13,
+  sym_section_name = 14,
+  sym_property = 15,
+  sym_value = 16,
+  sym_pair = 17,
+  sym__atom = 18,
+  aux_sym_source_file_repeat1 = 19,
+  aux_sym_source_file_repeat2 = 20,
+  aux_sym_section_repeat1 = 21,
+  aux_sym_value_repeat1 = 22,
+  anon_alias_sym_property_key = 23,
 };
 
 static const char * const ts_symbol_names[] = {
@@ -40,18 +47,25 @@ static const char * const ts_symbol_names[] = {
   [anon_sym_LBRACK] = "[",
   [anon_sym_RBRACK] = "]",
   [anon_sym_EQ] = "=",
-  [sym_joined_value] = "joined_value",
+  [anon_sym_COMMA] = ",",
   [anon_sym_SEMI] = ";",
-  [sym_id] = "id",
-  [aux_sym_number_token1] = "number_token1",
-  [aux_sym_number_token2] = "number_token2",
+  [anon_sym_PIPE] = "|",
+  [sym_number] = "number",
   [sym_identifier] = "identifier",
+  [sym_text] = "text",
+  [sym_comment] = "comment",
+  [sym__newline] = "_newline",
   [sym_source_file] = "source_file",
   [sym_section] = "section",
   [sym_section_name] = "section_name",
   [sym_property] = "property",
+  [sym_value] = "value",
+  [sym_pair] = "pair",
+  [sym__atom] = "_atom",
   [aux_sym_source_file_repeat1] = "source_file_repeat1",
+  [aux_sym_source_file_repeat2] = "source_file_repeat2",
   [aux_sym_section_repeat1] = "section_repeat1",
+  [aux_sym_value_repeat1] = "value_repeat1",
   [anon_alias_sym_property_key] = "property_key",
 };
 
@@ -60,18 +74,25 @@ static const TSSymbol ts_symbol_map[] = {
   [anon_sym_LBRACK] = anon_sym_LBRACK,
   [anon_sym_RBRACK] = anon_sym_RBRACK,
   [anon_sym_EQ] = anon_sym_EQ,
-  [sym_joined_value] = sym_joined_value,
+  [anon_sym_COMMA] = anon_sym_COMMA,
   [anon_sym_SEMI] = anon_sym_SEMI,
-  [sym_id] = sym_id,
-  [aux_sym_number_token1] = aux_sym_number_token1,
-  [aux_sym_number_token2] = aux_sym_number_token2,
+  [anon_sym_PIPE] = anon_sym_PIPE,
+  [sym_number] = sym_number,
   [sym_identifier] = sym_identifier,
+  [sym_text] = sym_text,
+  [sym_comment] = sym_comment,
+  [sym__newline] = sym__newline,
   [sym_source_file] = sym_source_file,
   [sym_section] = sym_section,
   [sym_section_name] = sym_section_name,
   [sym_property] = sym_property,
+  [sym_value] = sym_value,
+  [sym_pair] = sym_pair,
+  [sym__atom] = sym__atom,
   [aux_sym_source_file_repeat1] = aux_sym_source_file_repeat1,
+  [aux_sym_source_file_repeat2] = aux_sym_source_file_repeat2,
   [aux_sym_section_repeat1] = aux_sym_section_repeat1,
+  [aux_sym_value_repeat1] = aux_sym_value_repeat1,
   [anon_alias_sym_property_key] = anon_alias_sym_property_key,
 };
 
@@ -92,30 +113,38 @@ static const TSSymbolMetadata ts_symbol_metadata[] = {
     .visible = true,
     .named = false,
   },
-  [sym_joined_value] = {
+  [anon_sym_COMMA] = {
     .visible = true,
-    .named = true,
+    .named = false,
   },
   [anon_sym_SEMI] = {
     .visible = true,
     .named = false,
   },
-  [sym_id] = {
+  [anon_sym_PIPE] = {
     .visible = true,
-    .named = true,
-  },
-  [aux_sym_number_token1] = {
-    .visible = false,
     .named = false,
   },
-  [aux_sym_number_token2] = {
-    .visible = false,
-    .named = false,
+  [sym_number] = {
+    .visible = true,
+    .named = true,
   },
   [sym_identifier] = {
     .visible = true,
     .named = true,
   },
+  [sym_text] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_comment] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym__newline] = {
+    .visible = false,
+    .named = true,
+  },
   [sym_source_file] = {
     .visible = true,
     .named = true,
@@ -132,14 +161,34 @@ static const TSSymbolMetadata ts_symbol_metadata[] = {
     .visible = true,
     .named = true,
   },
+  [sym_value] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym_pair] = {
+    .visible = true,
+    .named = true,
+  },
+  [sym__atom] = {
+    .visible = false,
+    .named = true,
+  },
   [aux_sym_source_file_repeat1] = {
     .visible = false,
     .named = false,
   },
+  [aux_sym_source_file_repeat2] = {
+    .visible = false,
+    .named = false,
+  },
   [aux_sym_section_repeat1] = {
     .visible = false,
     .named = false,
   },
+  [aux_sym_value_repeat1] = {
+    .visible = false,
+    .named = false,
+  },
   [anon_alias_sym_property_key] = {
     .visible = true,
     .named = false,
@@ -172,6 +221,30 @@ static const TSStateId ts_primary_state_ids[STATE_COUNT] = {
   [11] = 11,
   [12] = 12,
   [13] = 13,
+  [14] = 14,
+  [15] = 15,
+  [16] = 16,
+  [17] = 17,
+  [18] = 18,
+  [19] = 19,
+  [20] = 20,
+  [21] = 21,
+  [22] = 22,
+  [23] = 23,
+  [24] = 24,
+  [25] = 25,
+  [26] = 26,
+  [27] = 27,
+  [28] = 28,
+  [29] = 29,
+  [30] = 30,
+  [31] = 31,
+  [32] = 32,
+  [33] = 33,
+  [34] = 34,
+  [35] = 35,
+  [36] = 36,
+  [37] = 37,
 };
 
 static bool ts_lex(TSLexer *lexer, TSStateId state) {
@@ -179,158 +252,204 @@ static bool ts_lex(TSLexer *lexer, TSStateId state) {
   eof = lexer->eof(lexer);
   switch (state) {
     case 0:
-      if (eof) ADVANCE(6);
-      if (lookahead == '0') ADVANCE(16);
-      if (lookahead == ';') ADVANCE(12);
-      if (lookahead == '=') ADVANCE(9);
-      if (lookahead == '[') ADVANCE(7);
-      if (lookahead == ']') ADVANCE(8);
+      if (eof) ADVANCE(2);
+      if (lookahead == '\n') ADVANCE(9);
       if (lookahead == '\t' ||
-          lookahead == '\n' ||
           lookahead == '\r' ||
           lookahead == ' ') SKIP(0)
-      if (('1' <= lookahead && lookahead <= '9')) ADVANCE(20);
-      if (('a' <= lookahead && lookahead <= 'z')) ADVANCE(25);
+      if (lookahead == ',') ADVANCE(6);
+      if (lookahead == '-') ADVANCE(10);
+      if (lookahead == '0') ADVANCE(12);
+      if (lookahead == ';') ADVANCE(7);
+      if (lookahead == '=') ADVANCE(5);
+      if (lookahead == '[') ADVANCE(3);
+      if (lookahead == ']') ADVANCE(4);
+      if (lookahead == '|') ADVANCE(8);
+      if (('1' <= lookahead && lookahead <= '9')) ADVANCE(11);
       if (('A' <= lookahead && lookahead <= 'Z') ||
-          lookahead == '_') ADVANCE(24);
+          lookahead == '_' ||
+          ('a' <= lookahead && lookahead <= 'z')) ADVANCE(15);
+      if (lookahead != 0) ADVANCE(16);
       END_STATE();
     case 1:
-      if (lookahead == '\n') SKIP(1)
+      if (eof) ADVANCE(2);
+      if (lookahead == '\n') ADVANCE(9);
       if (lookahead == '\t' ||
           lookahead == '\r' ||
-          lookahead == ' ') ADVANCE(10);
-      if (lookahead != 0) ADVANCE(11);
+          lookahead == ' ') SKIP(1)
+      if (lookahead == '#' ||
+          lookahead == ';') ADVANCE(19);
+      if (lookahead == '/') ADVANCE(20);
+      if (lookahead == '=') ADVANCE(5);
+      if (lookahead == '[') ADVANCE(3);
+      if (lookahead == ']') ADVANCE(4);
+      if (('A' <= lookahead && lookahead <= 'Z') ||
+          lookahead == '_' ||
+          ('a' <= lookahead && lookahead <= 'z')) ADVANCE(18);
       END_STATE();
     case 2:
-      if (('0' <= lookahead && lookahead <= '9') ||
-          ('A' <= lookahead && lookahead <= 'F') ||
-          ('a' <= lookahead && lookahead <= 'f')) ADVANCE(21);
+      ACCEPT_TOKEN(ts_builtin_sym_end);
       END_STATE();
     case 3:
-      if (('0' <= lookahead && lookahead <= '9') ||
-          ('A' <= lookahead && lookahead <= 'Z') ||
-          lookahead == '_') ADVANCE(13);
+      ACCEPT_TOKEN(anon_sym_LBRACK);
       END_STATE();
     case 4:
-      if (('0' <= lookahead && lookahead <= '9') ||
-          ('A' <= lookahead && lookahead <= 'Z') ||
-          lookahead == '_') ADVANCE(3);
+      ACCEPT_TOKEN(anon_sym_RBRACK);
       END_STATE();
     case 5:
-      if (eof) ADVANCE(6);
-      if (lookahead == '[') ADVANCE(7);
-      if (lookahead == '\t' ||
-          lookahead == '\n' ||
-          lookahead == '\r' ||
-          lookahead == ' ') SKIP(5)
-      if (('A' <= lookahead && lookahead <= 'Z') ||
-          lookahead == '_' ||
-          ('a' <= lookahead && lookahead <= 'z')) ADVANCE(25);
+      ACCEPT_TOKEN(anon_sym_EQ);
       END_STATE();
     case 6:
-      ACCEPT_TOKEN(ts_builtin_sym_end);
+      ACCEPT_TOKEN(anon_sym_COMMA);
       END_STATE();
     case 7:
-      ACCEPT_TOKEN(anon_sym_LBRACK);
+      ACCEPT_TOKEN(anon_sym_SEMI);
       END_STATE();
     case 8:
-      ACCEPT_TOKEN(anon_sym_RBRACK);
+      ACCEPT_TOKEN(anon_sym_PIPE);
       END_STATE();
     case 9:
-      ACCEPT_TOKEN(anon_sym_EQ);
+      ACCEPT_TOKEN(sym__newline);
       END_STATE();
     case 10:
-      ACCEPT_TOKEN(sym_joined_value);
+      ACCEPT_TOKEN(sym_text);
+      if (('0' <= lookahead && lookahead <= '9')) ADVANCE(11);
       if (lookahead == '\t' ||
           lookahead == '\r' ||
-          lookahead == ' ') ADVANCE(10);
+          lookahead == ' ') ADVANCE(17);
       if (lookahead != 0 &&
-          lookahead != '\n') ADVANCE(11);
+          lookahead != '\n' &&
+          lookahead != ',' &&
+          lookahead != ';' &&
+          lookahead != '=' &&
+          lookahead != '[' &&
+          lookahead != ']' &&
+          lookahead != '|') ADVANCE(16);
       END_STATE();
     case 11:
-      ACCEPT_TOKEN(sym_joined_value);
+      ACCEPT_TOKEN(sym_number);
+      if (('0' <= lookahead && lookahead <= '9')) ADVANCE(11);
+      if (lookahead == '\t' ||
+          lookahead == '\r' ||
+          lookahead == ' ') ADVANCE(17);
       if (lookahead != 0 &&
-          lookahead != '\n') ADVANCE(11);
+          lookahead != '\n' &&
+          lookahead != ',' &&
+          lookahead != ';' &&
+          lookahead != '=' &&
+          lookahead != '[' &&
+          lookahead != ']' &&
+          lookahead != '|') ADVANCE(16);
       END_STATE();
     case 12:
-      ACCEPT_TOKEN(anon_sym_SEMI);
+      ACCEPT_TOKEN(sym_number);
+      if (lookahead == 'x') ADVANCE(13);
+      if (('0' <= lookahead && lookahead <= '9')) ADVANCE(11);
+      if (lookahead == '\t' ||
+          lookahead == '\r' ||
+          lookahead == ' ') ADVANCE(17);
+      if (lookahead != 0 &&
+          lookahead != '\n' &&
+          lookahead != ',' &&
+          lookahead != ';' &&
+          lookahead != '=' &&
+          lookahead != '[' &&
+          lookahead != ']' &&
+          lookahead != '|') ADVANCE(16);
       END_STATE();
     case 13:
-      ACCEPT_TOKEN(sym_id);
+      ACCEPT_TOKEN(sym_text);
+      if (('0' <= lookahead && lookahead <= '9') ||
+          ('A' <= lookahead && lookahead <= 'F') ||
+          ('a' <= lookahead && lookahead <= 'f')) ADVANCE(14);
+      if (lookahead == '\t' ||
+          lookahead == '\r' ||
+          lookahead == ' ') ADVANCE(17);
+      if (lookahead != 0 &&
+          lookahead != '\n' &&
+          lookahead != ',' &&
+          lookahead != ';' &&
+          lookahead != '=' &&
+          lookahead != '[' &&
+          lookahead != ']' &&
+          lookahead != '|') ADVANCE(16);
       END_STATE();
     case 14:
-      ACCEPT_TOKEN(sym_id);
-      if (('0' <= lookahead && lookahead <= '9')) ADVANCE(18);
+      ACCEPT_TOKEN(sym_number);
+      if (('0' <= lookahead && lookahead <= '9') ||
+          ('A' <= lookahead && lookahead <= 'F') ||
+          ('a' <= lookahead && lookahead <= 'f')) ADVANCE(14);
+      if (lookahead == '\t' ||
+          lookahead == '\r' ||
+          lookahead == ' ') ADVANCE(17);
+      if (lookahead != 0 &&
+          lookahead != '\n' &&
+          lookahead != ',' &&
+          lookahead != ';' &&
+          lookahead != '=' &&
+          lookahead != '[' &&
+          lookahead != ']' &&
+          lookahead != '|') ADVANCE(16);
       END_STATE();
     case 15:
-      ACCEPT_TOKEN(sym_id);
+      ACCEPT_TOKEN(sym_identifier);
       if (('0' <= lookahead && lookahead <= '9') ||
           ('A' <= lookahead && lookahead <= 'Z') ||
           lookahead == '_' ||
-          ('a' <= lookahead && lookahead <= 'z')) ADVANCE(25);
+          ('a' <= lookahead && lookahead <= 'z')) ADVANCE(15);
+      if (lookahead == '\t' ||
+          lookahead == '\r' ||
+          lookahead == ' ') ADVANCE(17);
+      if (lookahead != 0 &&
+          lookahead != '\n' &&
+          lookahead != ',' &&
+          lookahead != ';' &&
+          lookahead != '=' &&
+          lookahead != '[' &&
+          lookahead != ']' &&
+          lookahead != '|') ADVANCE(16);
       END_STATE();
     case 16:
-      ACCEPT_TOKEN(aux_sym_number_token1);
-      if (lookahead == 'x') ADVANCE(2);
-      if (('0' <= lookahead && lookahead <= '9')) ADVANCE(19);
-      if (('A' <= lookahead && lookahead <= 'Z') ||
-          lookahead == '_') ADVANCE(4);
+      ACCEPT_TOKEN(sym_text);
+      if (lookahead == '\t' ||
+          lookahead == '\r' ||
+          lookahead == ' ') ADVANCE(17);
+      if (lookahead != 0 &&
+          lookahead != '\n' &&
+          lookahead != ',' &&
+          lookahead != ';' &&
+          lookahead != '=' &&
+          lookahead != '[' &&
+          lookahead != ']' &&
+          lookahead != '|') ADVANCE(16);
       END_STATE();
     case 17:
-      ACCEPT_TOKEN(aux_sym_number_token1);
-      if (('0' <= lookahead && lookahead <= '9')) ADVANCE(14);
-      if (('A' <= lookahead && lookahead <= 'Z') ||
-          lookahead == '_') ADVANCE(13);
+      if (lookahead == '\t' ||
+          lookahead == '\r' ||
+          lookahead == ' ') ADVANCE(17);
+      if (lookahead != 0 &&
+          lookahead != '\n' &&
+          lookahead != ',' &&
+          lookahead != ';' &&
+          lookahead != '=' &&
+          lookahead != '[' &&
+          lookahead != ']' &&
+          lookahead != '|') ADVANCE(16);
       END_STATE();
     case 18:
-      ACCEPT_TOKEN(aux_sym_number_token1);
-      if (('0' <= lookahead && lookahead <= '9')) ADVANCE(18);
-      END_STATE();
-    case 19:
-      ACCEPT_TOKEN(aux_sym_number_token1);
-      if (('0' <= lookahead && lookahead <= '9')) ADVANCE(17);
-      if (('A' <= lookahead && lookahead <= 'Z') ||
-          lookahead == '_') ADVANCE(3);
-      END_STATE();
-    case 20:
-      ACCEPT_TOKEN(aux_sym_number_token1);
-      if (('0' <= lookahead && lookahead <= '9')) ADVANCE(19);
-      if (('A' <= lookahead && lookahead <= 'Z') ||
-          lookahead == '_') ADVANCE(4);
-      END_STATE();
-    case 21:
-      ACCEPT_TOKEN(aux_sym_number_token2);
-      if (('0' <= lookahead && lookahead <= '9') ||
-          ('A' <= lookahead && lookahead <= 'F') ||
-          ('a' <= lookahead && lookahead <= 'f')) ADVANCE(21);
-      END_STATE();
-    case 22:
-      ACCEPT_TOKEN(sym_identifier);
-      if (('a' <= lookahead && lookahead <= 'z')) ADVANCE(25);
-      if (('0' <= lookahead && lookahead <= '9') ||
-          ('A' <= lookahead && lookahead <= 'Z') ||
-          lookahead == '_') ADVANCE(15);
-      END_STATE();
-    case 23:
       ACCEPT_TOKEN(sym_identifier);
-      if (('a' <= lookahead && lookahead <= 'z')) ADVANCE(25);
       if (('0' <= lookahead && lookahead <= '9') ||
           ('A' <= lookahead && lookahead <= 'Z') ||
-          lookahead == '_') ADVANCE(22);
+          lookahead == '_' ||
+          ('a' <= lookahead && lookahead <= 'z')) ADVANCE(18);
       END_STATE();
-    case 24:
-      ACCEPT_TOKEN(sym_identifier);
-      if (('a' <= lookahead && lookahead <= 'z')) ADVANCE(25);
-      if (('0' <= lookahead && lookahead <= '9') ||
-          ('A' <= lookahead && lookahead <= 'Z') ||
-          lookahead == '_') ADVANCE(23);
+    case 19:
+      ACCEPT_TOKEN(sym_comment);
+      if (lookahead != 0 &&
+          lookahead != '\n') ADVANCE(19);
       END_STATE();
-    case 25:
-      ACCEPT_TOKEN(sym_identifier);
-      if (('0' <= lookahead && lookahead <= '9') ||
-          ('A' <= lookahead && lookahead <= 'Z') ||
-          lookahead == '_' ||
-          ('a' <= lookahead && lookahead <= 'z')) ADVANCE(25);
+    case 20:
+      if (lookahead == '/') ADVANCE(19);
       END_STATE();
     default:
       return false;
@@ -339,19 +458,43 @@ static bool ts_lex(TSLexer *lexer, TSStateId state) {
 
 static const TSLexMode ts_lex_modes[STATE_COUNT] = {
   [0] = {.lex_state = 0},
-  [1] = {.lex_state = 0},
-  [2] = {.lex_state = 5},
-  [3] = {.lex_state = 0},
-  [4] = {.lex_state = 5},
-  [5] = {.lex_state = 0},
-  [6] = {.lex_state = 5},
-  [7] = {.lex_state = 5},
-  [8] = {.lex_state = 5},
-  [9] = {.lex_state = 5},
-  [10] = {.lex_state = 0},
-  [11] = {.lex_state = 0},
-  [12] = {.lex_state = 0},
+  [1] = {.lex_state = 1},
+  [2] = {.lex_state = 1},
+  [3] = {.lex_state = 1},
+  [4] = {.lex_state = 1},
+  [5] = {.lex_state = 1},
+  [6] = {.lex_state = 1},
+  [7] = {.lex_state = 1},
+  [8] = {.lex_state = 1},
+  [9] = {.lex_state = 1},
+  [10] = {.lex_state = 1},
+  [11] = {.lex_state = 1},
+  [12] = {.lex_state = 1},
   [13] = {.lex_state = 1},
+  [14] = {.lex_state = 1},
+  [15] = {.lex_state = 1},
+  [16] = {.lex_state = 1},
+  [17] = {.lex_state = 1},
+  [18] = {.lex_state = 1},
+  [19] = {.lex_state = 1},
+  [20] = {.lex_state = 1},
+  [21] = {.lex_state = 0},
+  [22] = {.lex_state = 1},
+  [23] = {.lex_state = 0},
+  [24] = {.lex_state = 0},
+  [25] = {.lex_state = 0},
+  [26] = {.lex_state = 0},
+  [27] = {.lex_state = 0},
+  [28] = {.lex_state = 0},
+  [29] = {.lex_state = 0},
+  [30] = {.lex_state = 0},
+  [31] = {.lex_state = 0},
+  [32] = {.lex_state = 0},
+  [33] = {.lex_state = 0},
+  [34] = {.lex_state = 0},
+  [35] = {.lex_state = 0},
+  [36] = {.lex_state = 0},
+  [37] = {.lex_state = 0},
 };
 
 static const uint16_t ts_parse_table[LARGE_STATE_COUNT][SYMBOL_COUNT] = {
@@ -360,132 +503,573 @@ static const uint16_t ts_parse_table[LARGE_STATE_COUNT][SYMBOL_COUNT] = {
     [anon_sym_LBRACK] = ACTIONS(1),
     [anon_sym_RBRACK] = ACTIONS(1),
     [anon_sym_EQ] = ACTIONS(1),
+    [anon_sym_COMMA] = ACTIONS(1),
     [anon_sym_SEMI] = ACTIONS(1),
-    [sym_id] = ACTIONS(1),
-    [aux_sym_number_token1] = ACTIONS(1),
-    [aux_sym_number_token2] = ACTIONS(1),
+    [anon_sym_PIPE] = ACTIONS(1),
+    [sym_number] = ACTIONS(1),
     [sym_identifier] = ACTIONS(1),
+    [sym_text] = ACTIONS(1),
+    [sym_comment] = ACTIONS(1),
+    [sym__newline] = ACTIONS(1),
   },
   [1] = {
-    [sym_source_file] = STATE(10),
-    [sym_section] = STATE(3),
-    [sym_section_name] = STATE(2),
+    [sym_source_file] = STATE(2),
+    [sym_section] = STATE(9),
+    [sym_section_name] = STATE(5),
     [aux_sym_source_file_repeat1] = STATE(3),
+    [aux_sym_source_file_repeat2] = STATE(4),
     [ts_builtin_sym_end] = ACTIONS(3),
     [anon_sym_LBRACK] = ACTIONS(5),
+    [sym_comment] = ACTIONS(7),
+    [sym__newline] = ACTIONS(9),
   },
 };
 
 static const uint16_t ts_small_parse_table[] = {
-  [0] = 3,
+  [0] = 1,
+    ACTIONS(11), 1,
+      ts_builtin_sym_end,
+  [4] = 8,
+    STATE(9), 1,
+      sym_section,
+    STATE(5), 1,
+      sym_section_name,
+    STATE(11), 1,
+      aux_sym_source_file_repeat1,
+    STATE(10), 1,
+      aux_sym_source_file_repeat2,
+    ACTIONS(13), 1,
+      ts_builtin_sym_end,
+    ACTIONS(5), 1,
+      anon_sym_LBRACK,
+    ACTIONS(7), 1,
+      sym_comment,
     ACTIONS(9), 1,
-      sym_identifier,
-    ACTIONS(7), 2,
+      sym__newline,
+  [29] = 5,
+    STATE(9), 1,
+      sym_section,
+    STATE(5), 1,
+      sym_section_name,
+    STATE(12), 1,
+      aux_sym_source_file_repeat2,
+    ACTIONS(15), 1,
       ts_builtin_sym_end,
+    ACTIONS(5), 1,
       anon_sym_LBRACK,
-    STATE(4), 2,
-      sym_property,
+  [45] = 3,
+    STATE(13), 1,
       aux_sym_section_repeat1,
-  [12] = 4,
-    ACTIONS(5), 1,
+    ACTIONS(17), 2,
+      ts_builtin_sym_end,
       anon_sym_LBRACK,
-    ACTIONS(11), 1,
+    ACTIONS(19), 1,
+      sym__newline,
+  [56] = 1,
+    ACTIONS(21), 1,
+      sym_identifier,
+  [60] = 2,
+    ACTIONS(23), 3,
       ts_builtin_sym_end,
-    STATE(2), 1,
-      sym_section_name,
-    STATE(5), 2,
+      anon_sym_LBRACK,
+      sym__newline,
+    ACTIONS(25), 1,
+      sym_comment,
+  [69] = 2,
+    ACTIONS(27), 3,
+      ts_builtin_sym_end,
+      anon_sym_LBRACK,
+      sym__newline,
+    ACTIONS(29), 1,
+      sym_comment,
+  [78] = 1,
+    ACTIONS(31), 2,
+      ts_builtin_sym_end,
+      anon_sym_LBRACK,
+  [83] = 5,
+    STATE(9), 1,
       sym_section,
+    STATE(5), 1,
+      sym_section_name,
+    STATE(12), 1,
+      aux_sym_source_file_repeat2,
+    ACTIONS(33), 1,
+      ts_builtin_sym_end,
+    ACTIONS(5), 1,
+      anon_sym_LBRACK,
+  [99] = 4,
+    STATE(11), 1,
       aux_sym_source_file_repeat1,
-  [26] = 3,
-    ACTIONS(9), 1,
-      sym_identifier,
-    ACTIONS(13), 2,
+    ACTIONS(35), 2,
       ts_builtin_sym_end,
       anon_sym_LBRACK,
-    STATE(6), 2,
+    ACTIONS(37), 1,
+      sym_comment,
+    ACTIONS(40), 1,
+      sym__newline,
+  [113] = 5,
+    STATE(9), 1,
+      sym_section,
+    STATE(5), 1,
+      sym_section_name,
+    STATE(12), 1,
+      aux_sym_source_file_repeat2,
+    ACTIONS(43), 1,
+      ts_builtin_sym_end,
+    ACTIONS(45), 1,
+      anon_sym_LBRACK,
+  [129] = 3,
+    STATE(16), 1,
+      aux_sym_section_repeat1,
+    ACTIONS(48), 2,
+      ts_builtin_sym_end,
+      anon_sym_LBRACK,
+    ACTIONS(19), 1,
+      sym__newline,
+  [140] = 4,
+    STATE(18), 1,
       sym_property,
+    ACTIONS(50), 3,
+      ts_builtin_sym_end,
+      anon_sym_LBRACK,
+      sym__newline,
+    ACTIONS(52), 1,
+      sym_identifier,
+    ACTIONS(54), 1,
+      sym_comment,
+  [155] = 1,
+    ACTIONS(56), 1,
+      anon_sym_RBRACK,
+  [159] = 3,
+    STATE(16), 1,
       aux_sym_section_repeat1,
-  [38] = 4,
-    ACTIONS(15), 1,
+    ACTIONS(58), 2,
       ts_builtin_sym_end,
-    ACTIONS(17), 1,
       anon_sym_LBRACK,
-    STATE(2), 1,
-      sym_section_name,
-    STATE(5), 2,
-      sym_section,
-      aux_sym_source_file_repeat1,
-  [52] = 3,
-    ACTIONS(22), 1,
+    ACTIONS(60), 1,
+      sym__newline,
+  [170] = 1,
+    ACTIONS(63), 1,
+      anon_sym_EQ,
+  [174] = 1,
+    ACTIONS(65), 3,
+      ts_builtin_sym_end,
+      anon_sym_LBRACK,
+      sym__newline,
+  [180] = 1,
+    ACTIONS(67), 3,
+      ts_builtin_sym_end,
+      anon_sym_LBRACK,
+      sym__newline,
+  [186] = 1,
+    ACTIONS(69), 3,
+      ts_builtin_sym_end,
+      anon_sym_LBRACK,
+      sym__newline,
+  [192] = 14,
+    STATE(22), 1,
+      sym_value,
+    STATE(28), 1,
+      sym_pair,
+    STATE(24), 1,
+      sym__atom,
+    STATE(23), 1,
+      aux_sym_value_repeat1,
+    ACTIONS(71), 2,
+      ts_builtin_sym_end,
+      sym__newline,
+    ACTIONS(73), 1,
+      anon_sym_LBRACK,
+    ACTIONS(75), 1,
+      anon_sym_RBRACK,
+    ACTIONS(77), 1,
+      anon_sym_EQ,
+    ACTIONS(79), 1,
+      anon_sym_COMMA,
+    ACTIONS(81), 1,
+      anon_sym_SEMI,
+    ACTIONS(83), 1,
+      anon_sym_PIPE,
+    ACTIONS(85), 1,
+      sym_number,
+    ACTIONS(87), 1,
       sym_identifier,
-    ACTIONS(20), 2,
+    ACTIONS(89), 1,
+      sym_text,
+  [236] = 1,
+    ACTIONS(91), 3,
       ts_builtin_sym_end,
       anon_sym_LBRACK,
-    STATE(6), 2,
-      sym_property,
-      aux_sym_section_repeat1,
-  [64] = 1,
-    ACTIONS(25), 3,
+      sym__newline,
+  [242] = 13,
+    STATE(28), 1,
+      sym_pair,
+    STATE(24), 1,
+      sym__atom,
+    STATE(35), 1,
+      aux_sym_value_repeat1,
+    ACTIONS(93), 2,
       ts_builtin_sym_end,
+      sym__newline,
+    ACTIONS(73), 1,
       anon_sym_LBRACK,
+    ACTIONS(75), 1,
+      anon_sym_RBRACK,
+    ACTIONS(77), 1,
+      anon_sym_EQ,
+    ACTIONS(79), 1,
+      anon_sym_COMMA,
+    ACTIONS(81), 1,
+      anon_sym_SEMI,
+    ACTIONS(83), 1,
+      anon_sym_PIPE,
+    ACTIONS(85), 1,
+      sym_number,
+    ACTIONS(87), 1,
       sym_identifier,
-  [70] = 1,
-    ACTIONS(27), 3,
+    ACTIONS(89), 1,
+      sym_text,
+  [283] = 3,
+    ACTIONS(95), 6,
       ts_builtin_sym_end,
       anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_COMMA,
+      anon_sym_PIPE,
+      sym__newline,
+    ACTIONS(97), 1,
+      anon_sym_EQ,
+    ACTIONS(99), 4,
+      anon_sym_SEMI,
+      sym_number,
       sym_identifier,
-  [76] = 1,
-    ACTIONS(29), 1,
+      sym_text,
+  [301] = 2,
+    ACTIONS(101), 7,
+      ts_builtin_sym_end,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_EQ,
+      anon_sym_COMMA,
+      anon_sym_PIPE,
+      sym__newline,
+    ACTIONS(103), 4,
+      anon_sym_SEMI,
+      sym_number,
       sym_identifier,
-  [80] = 1,
-    ACTIONS(31), 1,
+      sym_text,
+  [317] = 2,
+    ACTIONS(105), 7,
       ts_builtin_sym_end,
-  [84] = 1,
-    ACTIONS(33), 1,
+      anon_sym_LBRACK,
       anon_sym_RBRACK,
-  [88] = 1,
-    ACTIONS(35), 1,
       anon_sym_EQ,
-  [92] = 1,
-    ACTIONS(37), 1,
-      sym_joined_value,
+      anon_sym_COMMA,
+      anon_sym_PIPE,
+      sym__newline,
+    ACTIONS(107), 4,
+      anon_sym_SEMI,
+      sym_number,
+      sym_identifier,
+      sym_text,
+  [333] = 2,
+    ACTIONS(109), 7,
+      ts_builtin_sym_end,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_EQ,
+      anon_sym_COMMA,
+      anon_sym_PIPE,
+      sym__newline,
+    ACTIONS(111), 4,
+      anon_sym_SEMI,
+      sym_number,
+      sym_identifier,
+      sym_text,
+  [349] = 2,
+    ACTIONS(113), 7,
+      ts_builtin_sym_end,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_EQ,
+      anon_sym_COMMA,
+      anon_sym_PIPE,
+      sym__newline,
+    ACTIONS(115), 4,
+      anon_sym_SEMI,
+      sym_number,
+      sym_identifier,
+      sym_text,
+  [365] = 2,
+    ACTIONS(117), 7,
+      ts_builtin_sym_end,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_EQ,
+      anon_sym_COMMA,
+      anon_sym_PIPE,
+      sym__newline,
+    ACTIONS(119), 4,
+      anon_sym_SEMI,
+      sym_number,
+      sym_identifier,
+      sym_text,
+  [381] = 2,
+    ACTIONS(121), 7,
+      ts_builtin_sym_end,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_EQ,
+      anon_sym_COMMA,
+      anon_sym_PIPE,
+      sym__newline,
+    ACTIONS(123), 4,
+      anon_sym_SEMI,
+      sym_number,
+      sym_identifier,
+      sym_text,
+  [397] = 2,
+    ACTIONS(125), 7,
+      ts_builtin_sym_end,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_EQ,
+      anon_sym_COMMA,
+      anon_sym_PIPE,
+      sym__newline,
+    ACTIONS(127), 4,
+      anon_sym_SEMI,
+      sym_number,
+      sym_identifier,
+      sym_text,
+  [413] = 2,
+    ACTIONS(129), 7,
+      ts_builtin_sym_end,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_EQ,
+      anon_sym_COMMA,
+      anon_sym_PIPE,
+      sym__newline,
+    ACTIONS(131), 4,
+      anon_sym_SEMI,
+      sym_number,
+      sym_identifier,
+      sym_text,
+  [429] = 2,
+    ACTIONS(133), 7,
+      ts_builtin_sym_end,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_EQ,
+      anon_sym_COMMA,
+      anon_sym_PIPE,
+      sym__newline,
+    ACTIONS(135), 4,
+      anon_sym_SEMI,
+      sym_number,
+      sym_identifier,
+      sym_text,
+  [445] = 2,
+    ACTIONS(137), 7,
+      ts_builtin_sym_end,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_EQ,
+      anon_sym_COMMA,
+      anon_sym_PIPE,
+      sym__newline,
+    ACTIONS(139), 4,
+      anon_sym_SEMI,
+      sym_number,
+      sym_identifier,
+      sym_text,
+  [461] = 13,
+    STATE(28), 1,
+      sym_pair,
+    STATE(24), 1,
+      sym__atom,
+    STATE(35), 1,
+      aux_sym_value_repeat1,
+    ACTIONS(141), 2,
+      ts_builtin_sym_end,
+      sym__newline,
+    ACTIONS(143), 1,
+      anon_sym_LBRACK,
+    ACTIONS(146), 1,
+      anon_sym_RBRACK,
+    ACTIONS(149), 1,
+      anon_sym_EQ,
+    ACTIONS(152), 1,
+      anon_sym_COMMA,
+    ACTIONS(155), 1,
+      anon_sym_SEMI,
+    ACTIONS(158), 1,
+      anon_sym_PIPE,
+    ACTIONS(161), 1,
+      sym_number,
+    ACTIONS(164), 1,
+      sym_identifier,
+    ACTIONS(167), 1,
+      sym_text,
+  [502] = 6,
+    STATE(37), 1,
+      sym__atom,
+    ACTIONS(170), 7,
+      ts_builtin_sym_end,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_EQ,
+      anon_sym_COMMA,
+      anon_sym_PIPE,
+      sym__newline,
+    ACTIONS(172), 1,
+      anon_sym_SEMI,
+    ACTIONS(85), 1,
+      sym_number,
+    ACTIONS(87), 1,
+      sym_identifier,
+    ACTIONS(89), 1,
+      sym_text,
+  [527] = 2,
+    ACTIONS(174), 7,
+      ts_builtin_sym_end,
+      anon_sym_LBRACK,
+      anon_sym_RBRACK,
+      anon_sym_EQ,
+      anon_sym_COMMA,
+      anon_sym_PIPE,
+      sym__newline,
+    ACTIONS(176), 4,
+      anon_sym_SEMI,
+      sym_number,
+      sym_identifier,
+      sym_text,
 };
 
 static const uint32_t ts_small_parse_table_map[] = {
   [SMALL_STATE(2)] = 0,
-  [SMALL_STATE(3)] = 12,
-  [SMALL_STATE(4)] = 26,
-  [SMALL_STATE(5)] = 38,
-  [SMALL_STATE(6)] = 52,
-  [SMALL_STATE(7)] = 64,
-  [SMALL_STATE(8)] = 70,
-  [SMALL_STATE(9)] = 76,
-  [SMALL_STATE(10)] = 80,
-  [SMALL_STATE(11)] = 84,
-  [SMALL_STATE(12)] = 88,
-  [SMALL_STATE(13)] = 92,
+  [SMALL_STATE(3)] = 4,
+  [SMALL_STATE(4)] = 29,
+  [SMALL_STATE(5)] = 45,
+  [SMALL_STATE(6)] = 56,
+  [SMALL_STATE(7)] = 60,
+  [SMALL_STATE(8)] = 69,
+  [SMALL_STATE(9)] = 78,
+  [SMALL_STATE(10)] = 83,
+  [SMALL_STATE(11)] = 99,
+  [SMALL_STATE(12)] = 113,
+  [SMALL_STATE(13)] = 129,
+  [SMALL_STATE(14)] = 140,
+  [SMALL_STATE(15)] = 155,
+  [SMALL_STATE(16)] = 159,
+  [SMALL_STATE(17)] = 170,
+  [SMALL_STATE(18)] = 174,
+  [SMALL_STATE(19)] = 180,
+  [SMALL_STATE(20)] = 186,
+  [SMALL_STATE(21)] = 192,
+  [SMALL_STATE(22)] = 236,
+  [SMALL_STATE(23)] = 242,
+  [SMALL_STATE(24)] = 283,
+  [SMALL_STATE(25)] = 301,
+  [SMALL_STATE(26)] = 317,
+  [SMALL_STATE(27)] = 333,
+  [SMALL_STATE(28)] = 349,
+  [SMALL_STATE(29)] = 365,
+  [SMALL_STATE(30)] = 381,
+  [SMALL_STATE(31)] = 397,
+  [SMALL_STATE(32)] = 413,
+  [SMALL_STATE(33)] = 429,
+  [SMALL_STATE(34)] = 445,
+  [SMALL_STATE(35)] = 461,
+  [SMALL_STATE(36)] = 502,
+  [SMALL_STATE(37)] = 527,
 };
 
 static const TSParseActionEntry ts_parse_actions[] = {
   [0] = {.entry = {.count = 0, .reusable = false}},
   [1] = {.entry = {.count = 1, .reusable = false}}, RECOVER(),
   [3] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_source_file, 0),
-  [5] = {.entry = {.count = 1, .reusable = true}}, SHIFT(9),
-  [7] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_section, 1),
-  [9] = {.entry = {.count = 1, .reusable = true}}, SHIFT(12),
-  [11] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_source_file, 1),
-  [13] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_section, 2),
-  [15] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_source_file_repeat1, 2),
-  [17] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_source_file_repeat1, 2), SHIFT_REPEAT(9),
-  [20] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_section_repeat1, 2),
-  [22] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_section_repeat1, 2), SHIFT_REPEAT(12),
-  [25] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_section_name, 3),
-  [27] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_property, 3, .production_id = 1),
-  [29] = {.entry = {.count = 1, .reusable = true}}, SHIFT(11),
-  [31] = {.entry = {.count = 1, .reusable = true}},  ACCEPT_INPUT(),
-  [33] = {.entry = {.count = 1, .reusable = true}}, SHIFT(7),
-  [35] = {.entry = {.count = 1, .reusable = true}}, SHIFT(13),
-  [37] = {.entry = {.count = 1, .reusable = true}}, SHIFT(8),
+  [5] = {.entry = {.count = 1, .reusable = true}}, SHIFT(6),
+  [7] = {.entry = {.count = 1, .reusable = false}}, SHIFT(7),
+  [9] = {.entry = {.count = 1, .reusable = true}}, SHIFT(8),
+  [11] = {.entry = {.count = 1, .reusable = true}},  ACCEPT_INPUT(),
+  [13] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_source_file, 1),
+  [15] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_source_file, 1),
+  [17] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_section, 1),
+  [19] = {.entry = {.count = 1, .reusable = true}}, SHIFT(14),
+  [21] = {.entry = {.count = 1, .reusable = false}}, SHIFT(15),
+  [23] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_source_file_repeat1, 1),
+  [25] = {.entry = {.count = 1, .reusable = false}}, REDUCE(aux_sym_source_file_repeat1, 1),
+  [27] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_source_file_repeat1, 1),
+  [29] = {.entry = {.count = 1, .reusable = false}}, REDUCE(aux_sym_source_file_repeat1, 1),
+  [31] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_source_file_repeat2, 1),
+  [33] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_source_file, 2),
+  [35] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_source_file_repeat1, 2),
+  [37] = {.entry = {.count = 2, .reusable = false}}, REDUCE(aux_sym_source_file_repeat1, 2), SHIFT_REPEAT(7),
+  [40] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_source_file_repeat1, 2), SHIFT_REPEAT(8),
+  [43] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_source_file_repeat2, 2),
+  [45] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_source_file_repeat2, 2), SHIFT_REPEAT(6),
+  [48] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_section, 2),
+  [50] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_section_repeat1, 1),
+  [52] = {.entry = {.count = 1, .reusable = false}}, SHIFT(17),
+  [54] = {.entry = {.count = 1, .reusable = false}}, SHIFT(19),
+  [56] = {.entry = {.count = 1, .reusable = true}}, SHIFT(20),
+  [58] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_section_repeat1, 2),
+  [60] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_section_repeat1, 2), SHIFT_REPEAT(14),
+  [63] = {.entry = {.count = 1, .reusable = true}}, SHIFT(21),
+  [65] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_section_repeat1, 2),
+  [67] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_section_repeat1, 2),
+  [69] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_section_name, 3),
+  [71] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_property, 2, .production_id = 1),
+  [73] = {.entry = {.count = 1, .reusable = true}}, SHIFT(33),
+  [75] = {.entry = {.count = 1, .reusable = true}}, SHIFT(34),
+  [77] = {.entry = {.count = 1, .reusable = true}}, SHIFT(32),
+  [79] = {.entry = {.count = 1, .reusable = true}}, SHIFT(29),
+  [81] = {.entry = {.count = 1, .reusable = false}}, SHIFT(30),
+  [83] = {.entry = {.count = 1, .reusable = true}}, SHIFT(31),
+  [85] = {.entry = {.count = 1, .reusable = false}}, SHIFT(25),
+  [87] = {.entry = {.count = 1, .reusable = false}}, SHIFT(26),
+  [89] = {.entry = {.count = 1, .reusable = false}}, SHIFT(27),
+  [91] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_property, 3, .production_id = 1),
+  [93] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_value, 1),
+  [95] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_value_repeat1, 1),
+  [97] = {.entry = {.count = 1, .reusable = true}}, SHIFT(36),
+  [99] = {.entry = {.count = 1, .reusable = false}}, REDUCE(aux_sym_value_repeat1, 1),
+  [101] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym__atom, 1),
+  [103] = {.entry = {.count = 1, .reusable = false}}, REDUCE(sym__atom, 1),
+  [105] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym__atom, 1),
+  [107] = {.entry = {.count = 1, .reusable = false}}, REDUCE(sym__atom, 1),
+  [109] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym__atom, 1),
+  [111] = {.entry = {.count = 1, .reusable = false}}, REDUCE(sym__atom, 1),
+  [113] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_value_repeat1, 1),
+  [115] = {.entry = {.count = 1, .reusable = false}}, REDUCE(aux_sym_value_repeat1, 1),
+  [117] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_value_repeat1, 1),
+  [119] = {.entry = {.count = 1, .reusable = false}}, REDUCE(aux_sym_value_repeat1, 1),
+  [121] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_value_repeat1, 1),
+  [123] = {.entry = {.count = 1, .reusable = false}}, REDUCE(aux_sym_value_repeat1, 1),
+  [125] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_value_repeat1, 1),
+  [127] = {.entry = {.count = 1, .reusable = false}}, REDUCE(aux_sym_value_repeat1, 1),
+  [129] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_value_repeat1, 1),
+  [131] = {.entry = {.count = 1, .reusable = false}}, REDUCE(aux_sym_value_repeat1, 1),
+  [133] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_value_repeat1, 1),
+  [135] = {.entry = {.count = 1, .reusable = false}}, REDUCE(aux_sym_value_repeat1, 1),
+  [137] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_value_repeat1, 1),
+  [139] = {.entry = {.count = 1, .reusable = false}}, REDUCE(aux_sym_value_repeat1, 1),
+  [141] = {.entry = {.count = 1, .reusable = true}}, REDUCE(aux_sym_value_repeat1, 2),
+  [143] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_value_repeat1, 2), SHIFT_REPEAT(33),
+  [146] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_value_repeat1, 2), SHIFT_REPEAT(34),
+  [149] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_value_repeat1, 2), SHIFT_REPEAT(32),
+  [152] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_value_repeat1, 2), SHIFT_REPEAT(29),
+  [155] = {.entry = {.count = 2, .reusable = false}}, REDUCE(aux_sym_value_repeat1, 2), SHIFT_REPEAT(30),
+  [158] = {.entry = {.count = 2, .reusable = true}}, REDUCE(aux_sym_value_repeat1, 2), SHIFT_REPEAT(31),
+  [161] = {.entry = {.count = 2, .reusable = false}}, REDUCE(aux_sym_value_repeat1, 2), SHIFT_REPEAT(25),
+  [164] = {.entry = {.count = 2, .reusable = false}}, REDUCE(aux_sym_value_repeat1, 2), SHIFT_REPEAT(26),
+  [167] = {.entry = {.count = 2, .reusable = false}}, REDUCE(aux_sym_value_repeat1, 2), SHIFT_REPEAT(27),
+  [170] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_pair, 2),
+  [172] = {.entry = {.count = 1, .reusable = false}}, REDUCE(sym_pair, 2),
+  [174] = {.entry = {.count = 1, .reusable = true}}, REDUCE(sym_pair, 3),
+  [176] = {.entry = {.count = 1, .reusable = false}}, REDUCE(sym_pair, 3),
 };
 
 #ifdef __cplusplus